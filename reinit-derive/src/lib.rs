@@ -0,0 +1,325 @@
+//! Derive macros for the `reinit` crate.
+//!
+//! Use these through the `derive` feature of `reinit` rather than depending on this crate
+//! directly.
+
+#![deny(missing_docs,)]
+
+extern crate proc_macro;
+
+use proc_macro2::{Span, TokenStream,};
+use quote::{format_ident, quote,};
+use syn::{
+  parse_macro_input,
+  Data, DeriveInput, Error, Fields, GenericParam, Ident, Lifetime, Member, Type, Visibility,
+};
+
+/// Derives `reinit::Reinit` for a struct.
+///
+/// This generates a `{Name}Fields` struct holding an `Initialised` for each field of the
+/// struct which can be obtained using `Initialised::project`. Each field handle can be taken
+/// individually using the generated `take_{field}` method and reinitialised using the
+/// generated `init_{field}` method; once every field is initialised the handles can be
+/// rejoined using `reinit::Join`. `Uninitialised::project` produces the same struct with every
+/// field uninitialised so that a value can be built in place.
+#[proc_macro_derive(Reinit,)]
+pub fn derive_reinit(input: proc_macro::TokenStream,) -> proc_macro::TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+
+  reinit(input,).unwrap_or_else(Error::into_compile_error,).into()
+}
+
+/// Derives `reinit::Transition` for an enum.
+///
+/// The variant written if a transition panics is marked with `#[transition(fallback)]`; any
+/// fields it has are initialised using `Default::default()`.
+#[proc_macro_derive(Transition, attributes(transition,),)]
+pub fn derive_transition(input: proc_macro::TokenStream,) -> proc_macro::TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+
+  transition(input,).unwrap_or_else(Error::into_compile_error,).into()
+}
+
+/// A field of the deriving struct.
+struct Field {
+  vis: Visibility,
+  member: Member,
+  ty: Type,
+  /// The type parameter holding the handle for this field.
+  state: Ident,
+}
+
+fn reinit(input: DeriveInput,) -> syn::Result<TokenStream> {
+  let fields = match &input.data {
+    Data::Struct(data,) => &data.fields,
+    _ => return Err(Error::new(Span::call_site(), "`Reinit` can only be derived for structs",),),
+  };
+  for attr in input.attrs.iter().filter(|attr,| attr.path().is_ident("repr",),) {
+    attr.parse_nested_meta(|meta,| {
+      if meta.path.is_ident("packed",) {
+        return Err(meta.error("`Reinit` cannot be derived for packed structs",),)
+      }
+      //Skip any arguments such as those of `align(N)`.
+      if meta.input.peek(syn::token::Paren,) {
+        let _ = meta.input.parse::<proc_macro2::Group>()?;
+      }
+
+      Ok(())
+    },)?;
+  }
+
+  let DeriveInput { vis, ident: name, generics, .. } = &input;
+  let fields_name = format_ident!("{}Fields", name,);
+  let slot = match fields {
+    Fields::Named(_,) => Member::Named(format_ident!("__reinit_slot",),),
+    _ => Member::Unnamed(fields.len().into(),),
+  };
+  let fields = fields.iter().enumerate().map(|(index, field,),| Field {
+    vis: field.vis.clone(),
+    member: match &field.ident {
+      Some(ident,) => Member::Named(ident.clone(),),
+      None => Member::Unnamed(index.into(),),
+    },
+    ty: field.ty.clone(),
+    state: format_ident!("__ReinitField{}", index,),
+  },).collect::<Vec<_>>();
+  let lifetime = Lifetime::new("'__reinit", Span::call_site(),);
+  let (impl_generics, ty_generics, where_clause,) = generics.split_for_impl();
+  let params = generics.params.iter().map(|param,| match param {
+    GenericParam::Lifetime(param,) => { let param = &param.lifetime; quote!(#param) },
+    GenericParam::Type(param,) => { let param = &param.ident; quote!(#param) },
+    GenericParam::Const(param,) => { let param = &param.ident; quote!(#param) },
+  },).collect::<Vec<_>>();
+  let def_params = generics.params.iter().collect::<Vec<_>>();
+  //Defaults are not permitted on `impl` blocks.
+  let impl_params = generics.params.iter().cloned().map(|mut param,| {
+    match &mut param {
+      GenericParam::Type(param,) => param.default = None,
+      GenericParam::Const(param,) => param.default = None,
+      GenericParam::Lifetime(_,) => {},
+    }
+
+    param
+  },).collect::<Vec<_>>();
+  let predicates = where_clause.map(|clause,| clause.predicates.iter().collect::<Vec<_>>(),)
+    .unwrap_or_default();
+  let self_ty = quote!(#name #ty_generics);
+  let init_ty = |ty: &Type,| quote!(::reinit::Initialised<#lifetime, #ty>);
+  let base_ty = quote!(::reinit::Base<#lifetime, #self_ty>);
+
+  //The generated struct.
+  let defaults = fields.iter().map(|Field { ty, state, .. },| {
+    let init = init_ty(ty,);
+    quote!(#state = #init)
+  },);
+  let definition = {
+    let members = fields.iter().map(|Field { vis, member, state, .. },| match member {
+      Member::Named(member,) => quote!(#vis #member: #state),
+      Member::Unnamed(_,) => quote!(#vis #state),
+    },);
+    let doc = format!("Per-field handles projected from an `Initialised<{}>`.", name,);
+    let params = quote!(<#lifetime, #(#def_params,)* #(#defaults,)*>);
+    let bounds = quote!(where #self_ty: #lifetime, #(#predicates,)*);
+
+    match &slot {
+      Member::Named(slot,) => quote! {
+        #[doc = #doc]
+        #[must_use]
+        #vis struct #fields_name #params #bounds {
+          #(#members,)*
+          #slot: #base_ty,
+        }
+      },
+      Member::Unnamed(_,) => quote! {
+        #[doc = #doc]
+        #[must_use]
+        #vis struct #fields_name #params (#(#members,)* #base_ty,) #bounds;
+      },
+    }
+  };
+  //The type of the generated struct with `states` as the field handles.
+  let fields_ty = |states: &[TokenStream],| {
+    quote!(#fields_name<#lifetime, #(#params,)* #(#states,)*>)
+  };
+  //The type of the generated struct with every field uninitialised.
+  let fields_ty_uninit = || fields_ty(&fields.iter().map(|Field { ty, .. },| {
+    quote!(::reinit::Uninitialised<#lifetime, #ty>)
+  },).collect::<Vec<_>>(),);
+  let bounds = quote!(where #self_ty: #lifetime, #(#predicates,)*);
+  let members = fields.iter().map(|field,| &field.member,).collect::<Vec<_>>();
+
+  //Rejoining the fields.
+  let join = {
+    let fields_ty = fields_ty(&[],);
+
+    quote! {
+      impl<#lifetime, #(#impl_params,)*> ::reinit::Join for #fields_ty #bounds {
+        type Joined = ::reinit::Initialised<#lifetime, #self_ty>;
+
+        #[track_caller]
+        #[inline]
+        fn join(self,) -> Self::Joined {
+          let slot = self.#slot.as_ptr();
+          #(::reinit::__derive::check(
+            &self.#members,
+            unsafe { ::core::ptr::addr_of_mut!((*slot).#members) },
+          );)*
+
+          unsafe { self.#slot.into_initialised() }
+        }
+      }
+    }
+  };
+  //Taking and reinitialising individual fields.
+  let fields_impls = fields.iter().map(|field,| {
+    let Field { vis, member, ty, state, } = field;
+    let name = match member {
+      Member::Named(member,) => member.to_string(),
+      Member::Unnamed(member,) => member.index.to_string(),
+    };
+    let take = format_ident!("take_{}", name.trim_start_matches("r#",),);
+    let init = format_ident!("init_{}", name.trim_start_matches("r#",),);
+    let others = fields.iter().filter(|other,| other.state != *state,).map(|other,| &other.state,)
+      .collect::<Vec<_>>();
+    let with = |handle: TokenStream,| {
+      let states = fields.iter().map(|other,| if other.state == *state { handle.clone() } else {
+        let state = &other.state;
+        quote!(#state)
+      },).collect::<Vec<_>>();
+
+      fields_ty(&states,)
+    };
+    let initialised = with(init_ty(ty,),);
+    let uninitialised = with(quote!(::reinit::Uninitialised<#lifetime, #ty, __ReinitPolicy>),);
+    let taken = with(quote!(::reinit::Uninitialised<#lifetime, #ty>),);
+    let rest = members.iter().filter(|other,| ***other != *member,).collect::<Vec<_>>();
+    let take_doc = format!("Moves the value out of `{}` leaving it uninitialised.", name,);
+    let init_doc = format!("Reinitialises `{}`.", name,);
+
+    quote! {
+      impl<#lifetime, #(#impl_params,)* #(#others,)*> #initialised #bounds {
+        #[doc = #take_doc]
+        #[track_caller]
+        #[inline]
+        #vis fn #take(self,) -> (#ty, #taken,) {
+          let (value, uninit,) = self.#member.take();
+
+          (value, #fields_name { #member: uninit, #(#rest: self.#rest,)* #slot: self.#slot, },)
+        }
+      }
+
+      impl<#lifetime, #(#impl_params,)* #(#others,)* __ReinitPolicy,> #uninitialised
+        where #self_ty: #lifetime, __ReinitPolicy: ::reinit::DropPolicy<#ty>, #(#predicates,)* {
+        #[doc = #init_doc]
+        #[inline]
+        #vis fn #init(self, value: #ty,) -> #initialised {
+          let init = self.#member.init(value,);
+
+          #fields_name { #member: init, #(#rest: self.#rest,)* #slot: self.#slot, }
+        }
+      }
+    }
+  },);
+  //Projecting the fields.
+  let reinit = {
+    let fields_ty = fields_ty(&[],);
+    let uninit_ty = fields_ty_uninit();
+
+    quote! {
+      unsafe impl #impl_generics ::reinit::Reinit for #self_ty #where_clause {
+        type Fields<#lifetime> = #fields_ty where Self: #lifetime;
+        type UninitFields<#lifetime> = #uninit_ty where Self: #lifetime;
+
+        #[inline]
+        fn project<#lifetime>(
+          init: ::reinit::Initialised<#lifetime, Self>,
+        ) -> Self::Fields<#lifetime> {
+          let base = ::reinit::Base::new(init,);
+          let slot = base.as_ptr();
+
+          unsafe {
+            #fields_name {
+              #(#members: ::reinit::Initialised::new(
+                &mut *::core::ptr::addr_of_mut!((*slot).#members),
+              ),)*
+              #slot: base,
+            }
+          }
+        }
+        #[inline]
+        fn project_uninit<#lifetime, __ReinitPolicy,>(
+          uninit: ::reinit::Uninitialised<#lifetime, Self, __ReinitPolicy>,
+        ) -> Self::UninitFields<#lifetime>
+          where __ReinitPolicy: ::reinit::DropPolicy<Self>, {
+          let base = ::reinit::Base::from_uninit(uninit,);
+          let slot = base.as_ptr();
+
+          unsafe {
+            #fields_name {
+              #(#members: ::reinit::Uninitialised::from_raw(
+                ::core::ptr::addr_of_mut!((*slot).#members),
+              ),)*
+              #slot: base,
+            }
+          }
+        }
+      }
+    }
+  };
+  Ok(quote! {
+    #definition
+    #join
+    #(#fields_impls)*
+    #reinit
+  },)
+}
+
+fn transition(input: DeriveInput,) -> syn::Result<TokenStream> {
+  let variants = match &input.data {
+    Data::Enum(data,) => &data.variants,
+    _ => return Err(Error::new(Span::call_site(), "`Transition` can only be derived for enums",),),
+  };
+
+  let mut fallback = None;
+  for variant in variants {
+    for attr in variant.attrs.iter().filter(|attr,| attr.path().is_ident("transition",),) {
+      attr.parse_nested_meta(|meta,| {
+        if !meta.path.is_ident("fallback",) {
+          return Err(meta.error("expected `fallback`",),)
+        }
+        if fallback.is_some() {
+          return Err(meta.error("only one variant can be the `fallback`",),)
+        }
+
+        fallback = Some(variant,);
+        Ok(())
+      },)?;
+    }
+  }
+  let fallback = fallback.ok_or_else(|| Error::new(
+    Span::call_site(),
+    "`Transition` requires a variant marked `#[transition(fallback)]`",
+  ),)?;
+
+  let DeriveInput { ident: name, generics, .. } = &input;
+  let (impl_generics, ty_generics, where_clause,) = generics.split_for_impl();
+  let variant = &fallback.ident;
+  let value = match &fallback.fields {
+    Fields::Named(fields,) => {
+      let fields = fields.named.iter().map(|field,| &field.ident,);
+      quote!(Self::#variant { #(#fields: ::core::default::Default::default(),)* })
+    },
+    Fields::Unnamed(fields,) => {
+      let fields = fields.unnamed.iter().map(|_,| quote!(::core::default::Default::default()),);
+      quote!(Self::#variant(#(#fields,)*))
+    },
+    Fields::Unit => quote!(Self::#variant),
+  };
+
+  Ok(quote! {
+    impl #impl_generics ::reinit::Transition for #name #ty_generics #where_clause {
+      #[inline]
+      fn fallback() -> Self { #value }
+    }
+  },)
+}
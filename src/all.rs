@@ -0,0 +1,149 @@
+//! Taking and reinitialising several handles at once.
+
+use crate::*;
+
+/// A handle whose value can be replaced by [`take_all!`].
+///
+/// This is implemented for [`Initialised`] and `&mut T`.
+///
+/// # Safety
+///
+/// `into_raw` must return a pointer to an initialised `Value` which is valid for reads and
+/// writes until it is passed to `from_raw`. If `ABORT` is `false` the slot must be refilled by
+/// something else, such as [`Initialised::scope`], if the handle is leaked.
+pub unsafe trait TakeHandle: Sized {
+  /// The value behind the handle.
+  type Value;
+  /// `true` if the process must abort when the value is not replaced.
+  const ABORT: bool;
+
+  /// Converts the handle into a pointer to its value.
+  fn into_raw(self,) -> *mut Self::Value;
+  /// Converts a pointer returned by [`TakeHandle::into_raw`] back into the handle.
+  ///
+  /// # Safety
+  ///
+  /// `raw` must have been returned by `into_raw` for this type of handle and point to an
+  /// initialised value.
+  unsafe fn from_raw(raw: *mut Self::Value,) -> Self;
+}
+
+unsafe impl<T,> TakeHandle for &'_ mut T {
+  type Value = T;
+  const ABORT: bool = true;
+
+  #[inline]
+  fn into_raw(self,) -> *mut T { self }
+  #[inline]
+  unsafe fn from_raw(raw: *mut T,) -> Self { &mut *raw }
+}
+
+unsafe impl<T, S,> TakeHandle for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, {
+  type Value = T;
+  const ABORT: bool = false;
+
+  #[inline]
+  fn into_raw(self,) -> *mut T { Initialised::into_raw(self,) }
+  #[inline]
+  unsafe fn from_raw(raw: *mut T,) -> Self { Initialised { slot: raw, _phantom: PhantomData, } }
+}
+
+/// Replaces the values behind a tuple of handles, see [`take_all!`].
+pub trait ReplaceAll {
+  /// The values behind the handles.
+  type Values;
+
+  /// Replaces every value with the result of passing them to `f`, returning the handles.
+  fn replace_all<F,>(self, f: F,) -> Self
+    where F: FnOnce(Self::Values,) -> Self::Values;
+}
+
+/// Takes the values from several handles at once and reinitialises them.
+///
+/// `take_all!(a, b, .. => f)` takes the values from any mix of [`Initialised`] handles and
+/// `&mut T` references and passes them to `f` which must return a new value for every
+/// handle. The handles are returned once they have been reinitialised.
+///
+/// ```
+/// use reinit::*;
+///
+/// let (mut a, mut b) = (1, String::from("a"));
+/// take_all!(&mut a, &mut b => |(a, b)| (a + 1, b + "b"));
+/// assert_eq!((a, b.as_str()), (2, "ab"));
+///
+/// let mut c = vec![1];
+/// Initialised::scope(&mut c, Abort, |c| {
+///   let (c, _) = take_all!(c, &mut a => |(mut c, a)| { c.push(a); (c, a * 10) });
+///   (c, ())
+/// });
+/// assert_eq!((a, c), (20, vec![1, 2]));
+/// ```
+///
+/// If `f` panics the slots of any `Initialised` handles are left for [`Initialised::scope`] to
+/// refill; if any of the handles is a `&mut T` nothing can refill it so the process aborts.
+///
+/// Every handle must be given a value:
+///
+/// ```compile_fail
+/// use reinit::*;
+///
+/// let (mut a, mut b) = (1, 2);
+/// Initialised::scope(&mut a, Abort, |a| {
+///   let (a, _) = take_all!(a, &mut b => |(a, b)| (a + 1,));
+///   (a, ())
+/// });
+/// ```
+#[macro_export]
+macro_rules! take_all {
+  ($($handle:expr),+ $(,)? => $f:expr $(,)?) => {
+    $crate::ReplaceAll::replace_all(($($handle,)+), $f,)
+  };
+}
+
+/// Aborts the process if dropped while armed.
+struct AbortGuard(bool,);
+
+impl Drop for AbortGuard {
+  #[inline]
+  fn drop(&mut self,) {
+    if self.0 { abort(format_args!("Panicked while values were taken by `take_all!`",),) }
+  }
+}
+
+macro_rules! all {
+  ($(($($H:ident $i:tt,)+),)+) => {
+    $(all! { @impl $($H $i,)+ })+
+  };
+  (@impl $($H:ident $i:tt,)+) => {
+    impl<$($H,)+> ReplaceAll for ($($H,)+)
+      where $($H: TakeHandle,)+ {
+      type Values = ($($H::Value,)+);
+
+      fn replace_all<F,>(self, f: F,) -> Self
+        where F: FnOnce(Self::Values,) -> Self::Values, {
+        let slots = ($(self.$i.into_raw(),)+);
+        let guard = AbortGuard(false $(|| $H::ABORT)+,);
+        let values = f(unsafe { ($(ptr::read(slots.$i,),)+) },);
+        mem::forget(guard,);
+
+        unsafe { ($({ ptr::write(slots.$i, values.$i,); $H::from_raw(slots.$i,) },)+) }
+      }
+    }
+  };
+}
+
+all! {
+  (A 0,),
+  (A 0, B 1,),
+  (A 0, B 1, C 2,),
+  (A 0, B 1, C 2, D 3,),
+  (A 0, B 1, C 2, D 3, E 4,),
+  (A 0, B 1, C 2, D 3, E 4, F0 5,),
+  (A 0, B 1, C 2, D 3, E 4, F0 5, G 6,),
+  (A 0, B 1, C 2, D 3, E 4, F0 5, G 6, H 7,),
+  (A 0, B 1, C 2, D 3, E 4, F0 5, G 6, H 7, I 8,),
+  (A 0, B 1, C 2, D 3, E 4, F0 5, G 6, H 7, I 8, J 9,),
+  (A 0, B 1, C 2, D 3, E 4, F0 5, G 6, H 7, I 8, J 9, K 10,),
+  (A 0, B 1, C 2, D 3, E 4, F0 5, G 6, H 7, I 8, J 9, K 10, L 11,),
+}
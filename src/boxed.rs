@@ -0,0 +1,79 @@
+//! Reusing the allocation of a `Box`.
+
+use crate::*;
+use alloc::boxed::Box;
+use core::mem::MaybeUninit;
+
+/// An owned, uninitialised heap allocation for a `T`.
+///
+/// Unlike an [`Uninitialised`] this owns its memory so it can be stored and filled later;
+/// dropping it frees the allocation.
+///
+/// ```
+/// use reinit::BoxSlot;
+///
+/// let (s, slot) = BoxSlot::take(Box::new(String::from("hello")));
+/// let mut free_list = vec![slot];
+/// let boxed = free_list.pop().unwrap().init(s + " world");
+/// assert_eq!(*boxed, "hello world");
+/// ```
+pub struct BoxSlot<T,> {
+  /// The allocation.
+  slot: Box<MaybeUninit<T>>,
+}
+
+impl<T,> BoxSlot<T,> {
+  /// Allocates a new slot.
+  #[inline]
+  pub fn new() -> Self { Self { slot: Box::new(MaybeUninit::uninit(),), } }
+  /// Moves the value out of `boxed` keeping its allocation.
+  #[inline]
+  pub fn take(boxed: Box<T>,) -> (T, Self,) {
+    let slot = unsafe { Box::from_raw(Box::into_raw(boxed,) as *mut MaybeUninit<T>,) };
+    let value = unsafe { slot.assume_init_read() };
+
+    (value, Self { slot, },)
+  }
+  /// Fills the slot.
+  #[inline]
+  pub fn init(self, value: T,) -> Box<T> {
+    let slot = Box::into_raw(self.slot,);
+    unsafe {
+      (*slot).write(value,);
+      Box::from_raw(slot as *mut T,)
+    }
+  }
+  /// Reuses the slot for a `U` with the same size and alignment as `T`.
+  ///
+  /// Fails to compile if the layouts differ, see [`BoxSlot::try_cast`] for a runtime check.
+  #[inline]
+  pub fn cast<U,>(self,) -> BoxSlot<U,> {
+    const { assert!(same_layout::<T, U,>(), "`cast` requires types with the same layout",) }
+
+    self.cast_unchecked()
+  }
+  /// Reuses the slot for a `U` if it has the same size and alignment as `T`, otherwise returns
+  /// `self`.
+  #[inline]
+  pub fn try_cast<U,>(self,) -> Result<BoxSlot<U,>, Self> {
+    if same_layout::<T, U,>() { Ok(self.cast_unchecked(),) } else { Err(self,) }
+  }
+  /// Reuses the slot for a `U` without checking the layout.
+  #[inline]
+  fn cast_unchecked<U,>(self,) -> BoxSlot<U,> {
+    let slot = Box::into_raw(self.slot,) as *mut MaybeUninit<U>;
+
+    BoxSlot { slot: unsafe { Box::from_raw(slot,) }, }
+  }
+}
+
+impl<T,> Default for BoxSlot<T,> {
+  #[inline]
+  fn default() -> Self { Self::new() }
+}
+
+impl<T,> fmt::Debug for BoxSlot<T,> {
+  fn fmt(&self, fmt: &mut fmt::Formatter,) -> fmt::Result {
+    fmt.debug_struct(stringify!(BoxSlot),).finish_non_exhaustive()
+  }
+}
@@ -0,0 +1,156 @@
+//! A reference whose initialisation is checked at runtime.
+
+use crate::*;
+
+/// A reference which tracks whether it holds a value at runtime.
+///
+/// This suits loops and other control flow where a value is conditionally taken and replaced,
+/// which the [`Initialised`] and [`Uninitialised`] handles cannot express.
+///
+/// ```
+/// use reinit::*;
+///
+/// let mut buffer = Vec::new();
+/// Initialised::scope(&mut buffer, Abort, |init| {
+///   let mut slot = DynSlot::from(init);
+///   for i in 0..5 {
+///     match slot.take() {
+///       // Flush the buffer once it is full.
+///       Some(buffer) if buffer.len() == 2 => {},
+///       Some(mut buffer) => { buffer.push(i); slot.put(buffer); },
+///       None => { slot.put(vec![i]); },
+///     }
+///   }
+///   (slot.into_initialised().ok().unwrap(), ())
+/// });
+/// assert_eq!(buffer, [3, 4]);
+/// ```
+///
+/// Dropping the slot while it is empty reinitialises it using the [`DropPolicy`] `P`, by
+/// default [`LeakOnDrop`] which leaves the slot for [`Initialised::scope`] to refill so a panic
+/// while the slot is empty does not abort the process. A different policy can be chosen using
+/// [`DynSlot::with_policy`].
+#[must_use]
+pub struct DynSlot<'a, T: 'a, P = LeakOnDrop,>
+  where P: DropPolicy<T,>, {
+  /// The reference.
+  slot: *mut T,
+  /// Whether the reference holds a value.
+  filled: bool,
+  /// Where the value was last taken.
+  origin: Origin,
+  _phantom: PhantomData<&'a mut T>,
+  _policy: PhantomData<fn() -> P>,
+}
+
+impl<'a, T, P,> DynSlot<'a, T, P,>
+  where P: DropPolicy<T,>, {
+  /// Returns `true` if the reference holds a value.
+  #[inline]
+  pub fn is_filled(&self,) -> bool { self.filled }
+  /// Returns the value, if there is one.
+  #[inline]
+  pub fn get(&self,) -> Option<&T> {
+    if self.filled { Some(unsafe { &*self.slot }) } else { None }
+  }
+  /// Returns the value mutably, if there is one.
+  #[inline]
+  pub fn get_mut(&mut self,) -> Option<&mut T> {
+    if self.filled { Some(unsafe { &mut *self.slot }) } else { None }
+  }
+  /// Moves the value out of the reference, if there is one.
+  #[track_caller]
+  #[inline]
+  pub fn take(&mut self,) -> Option<T> {
+    if !self.filled { return None }
+
+    self.filled = false;
+    self.origin = Origin::new::<T,>();
+    Some(unsafe { ptr::read(self.slot,) })
+  }
+  /// Fills the reference with `value` returning the previous value, if there was one.
+  #[inline]
+  pub fn put(&mut self, value: T,) -> Option<T> {
+    if mem::replace(&mut self.filled, true,) {
+      Some(unsafe { ptr::replace(self.slot, value,) })
+    } else {
+      unsafe { ptr::write(self.slot, value,) };
+      None
+    }
+  }
+  /// Returns an [`Initialised`] handle if the reference holds a value or `self` otherwise.
+  #[inline]
+  pub fn into_initialised(self,) -> Result<Initialised<'a, T,>, Self> {
+    if !self.filled { return Err(self) }
+
+    let slot = mem::ManuallyDrop::new(self,).slot;
+    Ok(unsafe { Initialised::from_raw(slot,) })
+  }
+  /// Changes the policy used if this value is dropped while empty.
+  ///
+  /// ```
+  /// use reinit::*;
+  ///
+  /// let mut num = 1;
+  /// // SAFETY: `slot` is dropped rather than leaked so `num` is reinitialised.
+  /// let init = unsafe { Initialised::new(&mut num) };
+  /// let mut slot = DynSlot::from(init).with_policy::<DefaultOnDrop>();
+  /// slot.take();
+  /// drop(slot);
+  /// assert_eq!(num, 0);
+  /// ```
+  #[inline]
+  pub fn with_policy<Q,>(self,) -> DynSlot<'a, T, Q,>
+    where Q: DropPolicy<T,>, {
+    let Self { slot, filled, origin, .. } = *mem::ManuallyDrop::new(self,);
+
+    DynSlot { slot, filled, origin, _phantom: PhantomData, _policy: PhantomData, }
+  }
+  /// Returns an [`Uninitialised`] handle if the reference is empty or `self` otherwise.
+  #[inline]
+  pub fn into_uninitialised(self,) -> Result<Uninitialised<'a, T, P,>, Self> {
+    if self.filled { return Err(self) }
+
+    let Self { slot, origin, .. } = *mem::ManuallyDrop::new(self,);
+    Ok(Uninitialised { slot, origin, _phantom: PhantomData, _policy: PhantomData, })
+  }
+}
+
+impl<'a, T,> From<Initialised<'a, T,>> for DynSlot<'a, T,> {
+  #[track_caller]
+  #[inline]
+  fn from(from: Initialised<'a, T,>,) -> Self {
+    Self {
+      slot: from.into_inner(),
+      filled: true,
+      origin: Origin::new::<T,>(),
+      _phantom: PhantomData,
+      _policy: PhantomData,
+    }
+  }
+}
+
+impl<'a, T, P,> From<Uninitialised<'a, T, P,>> for DynSlot<'a, T, P,>
+  where P: DropPolicy<T,>, {
+  #[inline]
+  fn from(from: Uninitialised<'a, T, P,>,) -> Self {
+    let Uninitialised { slot, origin, .. } = *mem::ManuallyDrop::new(from,);
+    Self { slot, filled: false, origin, _phantom: PhantomData, _policy: PhantomData, }
+  }
+}
+
+impl<T: fmt::Debug, P,> fmt::Debug for DynSlot<'_, T, P,>
+  where P: DropPolicy<T,>, {
+  fn fmt(&self, fmt: &mut fmt::Formatter,) -> fmt::Result {
+    fmt.debug_tuple(stringify!(DynSlot),).field(&self.get(),).finish()
+  }
+}
+
+impl<T, P,> Drop for DynSlot<'_, T, P,>
+  where P: DropPolicy<T,>, {
+  #[track_caller]
+  #[inline]
+  fn drop(&mut self,) {
+    if !self.filled && P::REFILLS { unsafe { ptr::write(self.slot, P::on_drop(&self.origin,),) } }
+  }
+}
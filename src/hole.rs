@@ -0,0 +1,140 @@
+//! A panic safe hole in a slice.
+
+use crate::*;
+
+/// A slice with one element moved out of it.
+///
+/// The hole can be moved around the slice by shifting elements into it and is filled with the
+/// moved out element when this value is dropped or [`Hole::fill`]ed, even while unwinding.
+///
+/// ```
+/// use reinit::*;
+///
+/// /// Inserts the last element of `init` into the sorted elements before it.
+/// fn insert_tail(init: Initialised<[i32]>) -> Initialised<[i32]> {
+///   let len = init.len();
+///   let mut hole = Hole::new(init, len - 1);
+///   while hole.pos() > 0 && *hole.get(hole.pos() - 1) > *hole.element() {
+///     hole.move_to(hole.pos() - 1);
+///   }
+///   hole.fill()
+/// }
+///
+/// let mut values = [1, 3, 4, 2];
+/// Initialised::scope(&mut values[..], Abort, |init| (insert_tail(init), ()));
+/// assert_eq!(values, [1, 2, 3, 4]);
+/// ```
+pub struct Hole<'a, T,> {
+  /// The start of the slice.
+  slot: *mut T,
+  /// The length of the slice.
+  len: usize,
+  /// The element moved out of the slice.
+  element: mem::ManuallyDrop<T>,
+  /// The position of the hole.
+  hole: mem::ManuallyDrop<Uninitialised<'a, T,>>,
+  /// The index of the hole.
+  pos: usize,
+}
+
+impl<'a, T,> Hole<'a, T,> {
+  /// Moves the element at `pos` out of `init` leaving a hole.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `pos` is out of bounds.
+  #[track_caller]
+  #[inline]
+  pub fn new(init: Initialised<'a, [T],>, pos: usize,) -> Self {
+    let slice = init.into_inner();
+    assert!(pos < slice.len(), "Hole position out of bounds",);
+
+    let (slot, len,) = (slice.as_mut_ptr(), slice.len(),);
+    let (element, hole,) = unsafe { Initialised::new(&mut *slot.add(pos,),) }.take();
+
+    Self {
+      slot,
+      len,
+      element: mem::ManuallyDrop::new(element,),
+      hole: mem::ManuallyDrop::new(hole,),
+      pos,
+    }
+  }
+  /// The index of the hole.
+  #[inline]
+  pub fn pos(&self,) -> usize { self.pos }
+  /// The length of the slice.
+  #[inline]
+  pub fn len(&self,) -> usize { self.len }
+  /// Returns `true` if the slice is empty, which is never the case as it contains the hole.
+  #[inline]
+  pub fn is_empty(&self,) -> bool { false }
+  /// The element moved out of the slice.
+  #[inline]
+  pub fn element(&self,) -> &T { &self.element }
+  /// The element moved out of the slice.
+  #[inline]
+  pub fn element_mut(&mut self,) -> &mut T { &mut self.element }
+  /// Returns the element at `index`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index` is out of bounds or is the hole.
+  #[track_caller]
+  #[inline]
+  pub fn get(&self, index: usize,) -> &T {
+    self.check(index,);
+    unsafe { &*self.slot.add(index,) }
+  }
+  /// Returns the element at `index`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index` is out of bounds or is the hole.
+  #[track_caller]
+  #[inline]
+  pub fn get_mut(&mut self, index: usize,) -> &mut T {
+    self.check(index,);
+    unsafe { &mut *self.slot.add(index,) }
+  }
+  /// Moves the element at `index` into the hole leaving the hole at `index`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index` is out of bounds or is the hole.
+  #[track_caller]
+  #[inline]
+  pub fn move_to(&mut self, index: usize,) {
+    self.check(index,);
+
+    let (value, hole,) = unsafe { Initialised::new(&mut *self.slot.add(index,),) }.take();
+    let filled = mem::replace(&mut *self.hole, hole,);
+    let _ = filled.init(value,);
+    self.pos = index;
+  }
+  /// Fills the hole with the moved out element.
+  #[inline]
+  pub fn fill(self,) -> Initialised<'a, [T],> {
+    let (slot, len,) = (self.slot, self.len,);
+    drop(self,);
+
+    unsafe { Initialised::new(&mut *ptr::slice_from_raw_parts_mut(slot, len,),) }
+  }
+  /// Panics if `index` cannot be accessed.
+  #[track_caller]
+  #[inline]
+  fn check(&self, index: usize,) {
+    assert!(index < self.len, "Index out of bounds",);
+    assert!(index != self.pos, "Index is the hole",);
+  }
+}
+
+impl<T,> Drop for Hole<'_, T,> {
+  #[inline]
+  fn drop(&mut self,) {
+    unsafe {
+      let element = mem::ManuallyDrop::take(&mut self.element,);
+      let _ = mem::ManuallyDrop::take(&mut self.hole,).init(element,);
+    }
+  }
+}
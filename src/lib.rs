@@ -4,120 +4,1394 @@
 //! use reinit::*;
 //! 
 //! let mut n = 42;
-//! let init = Initialised::new(&mut n);
-//! let (v, uninit,) = init.take();
-//! assert_eq!(v, 42);
-//! let init = uninit.init(24);
+//! Initialised::scope(&mut n, Abort, |init| {
+//!   let (v, uninit,) = init.take();
+//!   assert_eq!(v, 42);
+//!   (uninit.init(24), ())
+//! });
 //! assert_eq!(n, 24);
 //! ```
 //! 
+//! An `Initialised` can only be safely constructed by [`Initialised::scope`] which requires
+//! the reference to be reinitialised before it returns, so leaking an `Uninitialised` can
+//! never expose moved from memory.
+//! 
 //! Author --- DMorgan  
 //! Last Moddified --- 2021-04-07
 
-#![no_std]
-#![deny(missing_docs,)]
-#![feature(
-  const_ptr_read, const_maybe_uninit_as_ptr, const_refs_to_cell, const_mut_refs,
-  const_ptr_write, const_raw_ptr_deref, const_panic, const_fn_transmute,
-)]
+#![no_std]
+#![deny(missing_docs,)]
+#![cfg_attr(feature = "nightly", feature(const_precise_live_drops,),)]
+#![cfg_attr(all(feature = "nightly", feature = "debug-location",), feature(const_type_name,),)]
+
+#[cfg(test,)]
+extern crate self as reinit;
+#[cfg(feature = "alloc",)]
+extern crate alloc;
+
+#[cfg(feature = "derive",)]
+pub use reinit_derive::{Reinit, Transition,};
+
+mod slot;
+mod split;
+mod slice;
+mod hole;
+mod transaction;
+mod all;
+mod option;
+mod dyn_slot;
+#[cfg(feature = "alloc",)]
+mod vec;
+#[cfg(feature = "alloc",)]
+mod boxed;
+
+pub use self::{slot::*, slice::*, hole::*, transaction::*, all::*, option::*, dyn_slot::*,};
+#[cfg(feature = "alloc",)]
+pub use self::{vec::*, boxed::*,};
+
+use core::{
+  ops::{Deref, DerefMut,},
+  borrow::{Borrow, BorrowMut,},
+  cmp::Ordering,
+  hash::{Hash, Hasher,},
+  marker::PhantomData,
+  fmt, mem, ptr,
+};
+
+/// Declares a function which is only `const` when the `nightly` feature is enabled.
+macro_rules! const_fn {
+  ($(#[$attr:meta])* $vis:vis unsafe fn $($item:tt)*) => {
+    #[cfg(feature = "nightly",)]
+    $(#[$attr])* $vis const unsafe fn $($item)*
+    #[cfg(not(feature = "nightly",),)]
+    $(#[$attr])* $vis unsafe fn $($item)*
+  };
+  ($(#[$attr:meta])* $vis:vis fn $($item:tt)*) => {
+    #[cfg(feature = "nightly",)]
+    $(#[$attr])* $vis const fn $($item)*
+    #[cfg(not(feature = "nightly",),)]
+    $(#[$attr])* $vis fn $($item)*
+  };
+}
+
+/// Aborts the process.
+/// 
+/// `core` has no stable abort so this panics while a value whose destructor also panics is
+/// live; a panic during unwinding aborts.
+#[cold]
+#[inline(never)]
+fn abort(msg: fmt::Arguments,) -> ! {
+  struct Bomb;
+
+  impl Drop for Bomb {
+    #[inline]
+    fn drop(&mut self,) { panic!("Aborting") }
+  }
+
+  let _bomb = Bomb;
+  panic!("{}", msg,)
+}
+
+/// Determines how the slot is refilled if the closure passed to [`Initialised::scope`] or
+/// [`Initialised::take_with`] panics.
+/// 
+/// For slices the value is cloned to refill each element.
+/// 
+/// Closures returning a `T` can be used to produce a fallback value.
+pub trait OnPanic<T,> {
+  /// Produces the value to write back into the slot.
+  /// 
+  /// This is called while unwinding; if it panics the process aborts.
+  fn recover(self,) -> T;
+}
+
+/// Aborts the process if the closure panics.
+/// 
+/// The process aborts whether or not the value was taken when the panic occurred.
+#[derive(Clone, Copy, Debug, Default,)]
+pub struct Abort;
+
+impl<T,> OnPanic<T,> for Abort {
+  #[inline]
+  fn recover(self,) -> T {
+    abort(format_args!("Panicked while an `Initialised` value was taken",),)
+  }
+}
+
+/// Restores `T::default()` if the closure panics.
+#[derive(Clone, Copy, Debug, Default,)]
+pub struct RestoreDefault;
+
+impl<T,> OnPanic<T,> for RestoreDefault
+  where T: Default, {
+  #[inline]
+  fn recover(self,) -> T { T::default() }
+}
+
+/// Restores a fixed value if the closure panics.
+#[derive(Clone, Copy, Debug, Default,)]
+pub struct Restore<T,>(pub T,);
+
+impl<T,> OnPanic<T,> for Restore<T,> {
+  #[inline]
+  fn recover(self,) -> T { self.0 }
+}
+
+impl<T, F,> OnPanic<T,> for F
+  where F: FnOnce() -> T, {
+  #[inline]
+  fn recover(self,) -> T { self() }
+}
+
+/// Refills a slot while unwinding out of [`Initialised::scope`].
+/// 
+/// This is implemented for every [`OnPanic`] and, for slices, every `Clone` [`OnPanic`] which
+/// is used to refill each element.
+/// 
+/// # Safety
+/// 
+/// `refill` must initialise the slot or diverge.
+pub unsafe trait Refill<T: ?Sized,> {
+  /// Overwrites `slot` without dropping its previous contents.
+  /// 
+  /// # Safety
+  /// 
+  /// `slot` must be valid for writes.
+  unsafe fn refill(self, slot: *mut T,);
+}
+
+unsafe impl<T, P,> Refill<T,> for P
+  where P: OnPanic<T,>, {
+  #[inline]
+  unsafe fn refill(self, slot: *mut T,) { ptr::write(slot, self.recover(),) }
+}
+
+unsafe impl<T, P,> Refill<[T],> for P
+  where P: OnPanic<T,> + Clone, {
+  #[inline]
+  unsafe fn refill(self, slot: *mut [T],) {
+    let elements = slot as *mut T;
+    for index in 0..slot.len() { ptr::write(elements.add(index,), self.clone().recover(),) }
+  }
+}
+
+/// Refills `slot` using `on_panic` when dropped.
+/// 
+/// Used to refill a slot while unwinding; forget it once the slot has been refilled.
+struct Guard<T: ?Sized, P,>
+  where P: Refill<T,>, {
+  slot: *mut T,
+  on_panic: mem::ManuallyDrop<P>,
+}
+
+impl<T: ?Sized, P,> Guard<T, P,>
+  where P: Refill<T,>, {
+  #[inline]
+  fn new(slot: *mut T, on_panic: P,) -> Self {
+    Self { slot, on_panic: mem::ManuallyDrop::new(on_panic,), }
+  }
+  /// Disarms the guard.
+  #[inline]
+  fn forget(self,) {
+    let mut this = mem::ManuallyDrop::new(self,);
+    unsafe { mem::ManuallyDrop::drop(&mut this.on_panic,) }
+  }
+}
+
+impl<T: ?Sized, P,> Drop for Guard<T, P,>
+  where P: Refill<T,>, {
+  #[inline]
+  fn drop(&mut self,) {
+    unsafe {
+      let on_panic = mem::ManuallyDrop::take(&mut self.on_panic,);
+      on_panic.refill(self.slot,);
+    }
+  }
+}
+
+/// A reference to initialised memory.
+/// 
+/// Derefs to the referenced value so it can be used in place of a `&mut T`.
+/// 
+/// The value is referenced through a [`Slot`] which defaults to a `&'a mut T`; other slots
+/// are constructed using [`Initialised::from_slot`].
+#[repr(transparent,)]
+pub struct Initialised<'a, T: 'a + ?Sized, S = &'a mut T,>
+  where S: Slot<Target = T,>, {
+  /// The reference.
+  slot: *mut T,
+  _phantom: PhantomData<(&'a mut T, S,)>,
+}
+
+impl<'a, T: ?Sized,> Initialised<'a, T,> {
+  const_fn! {
+    /// Constructs a new `Initialised` from `slot`.
+    /// 
+    /// # Safety
+    /// 
+    /// `slot` must be initialised when `'a` ends even if an `Uninitialised` derived from this
+    /// value is leaked or dropped using [`LeakOnDrop`]; prefer [`Initialised::scope`].
+    #[inline]
+    pub unsafe fn new(slot: &'a mut T,) -> Self { Self { slot, _phantom: PhantomData, } }
+  }
+  const_fn! {
+    /// Constructs a new `Initialised` from a raw pointer such as one received over FFI.
+    /// 
+    /// # Safety
+    /// 
+    /// `raw` must point to an initialised `T` which is valid for reads and writes and not
+    /// otherwise accessed for `'a`; the requirements of [`Initialised::new`] also apply.
+    #[inline]
+    pub unsafe fn from_raw(raw: *mut T,) -> Self { Self { slot: raw, _phantom: PhantomData, } }
+  }
+  /// Passes an `Initialised` for `slot` to `f` which must return an `Initialised` for the
+  /// same slot, proving that it was reinitialised.
+  /// 
+  /// If `f` panics, or returns an `Initialised` for a different slot, `slot` is refilled
+  /// using `on_panic` without dropping its previous contents. This happens even if the value
+  /// was never taken, in which case it is leaked, and with [`Abort`] any panic in `f` aborts
+  /// the process. An [`Uninitialised`] dropped while unwinding leaves its slot to be refilled
+  /// here unless it uses a policy such as [`PanicOnDrop`].
+  /// 
+  /// Safe code can only construct an `Initialised<T>` referencing its slot through a `&mut T`
+  /// inside `scope`, so `f` cannot return a handle for an unrelated slot which happens to
+  /// share its address such as that of a zero sized type:
+  /// 
+  /// ```compile_fail
+  /// use core::mem::MaybeUninit;
+  /// use reinit::*;
+  /// 
+  /// let mut unit = ();
+  /// Initialised::scope(&mut unit, Abort, |init| {
+  ///   let (_, uninit) = init.take();
+  ///   core::mem::forget(uninit);
+  ///   let other = Box::leak(Box::new(MaybeUninit::uninit()));
+  ///   (Uninitialised::from_maybe_uninit(other).init(()), ())
+  /// });
+  /// ```
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut s = String::from("hello");
+  /// let len = Initialised::scope(&mut s, Abort, |init| {
+  ///   let (s, uninit,) = init.take();
+  ///   let len = s.len();
+  ///   (uninit.init(s + " world"), len)
+  /// });
+  /// assert_eq!(len, 5);
+  /// assert_eq!(s, "hello world");
+  /// ```
+  pub fn scope<P, F, R,>(slot: &mut T, on_panic: P, f: F,) -> R
+    where P: Refill<T,>, F: for<'id> FnOnce(Initialised<'id, T,>,) -> (Initialised<'id, T,>, R,), {
+    let slot = slot as *mut T;
+    let guard = Guard::new(slot, on_panic,);
+    let (init, res,) = f(Initialised { slot, _phantom: PhantomData, },);
+    if !ptr::eq(init.slot, slot,) {
+      panic!(concat!("`", stringify!(scope), "` returned an `Initialised` for a different slot",),)
+    }
+
+    guard.forget();
+    res
+  }
+  const_fn! {
+    /// Returns the inner value.
+    #[inline]
+    pub fn into_inner(self,) -> &'a mut T {
+      let slot = self.slot;
+      mem::forget(self,);
+
+      unsafe { &mut *slot }
+    }
+  }
+  /// Rejoins handles split from an `Initialised`.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if the handles were not all split from the same value.
+  #[track_caller]
+  #[inline]
+  pub fn join<J,>(parts: J,) -> Self
+    where J: Join<Joined = Self,>, { parts.join() }
+}
+
+impl<'a, T: ?Sized, S,> Initialised<'a, T, S,>
+  where S: Slot<Target = T,>, {
+  /// Constructs a new `Initialised` from `slot`.
+  /// 
+  /// # Safety
+  /// 
+  /// `slot` must be initialised and remain so once `'a` ends, or the slot is next used, even
+  /// if an `Uninitialised` derived from this value is leaked or dropped using [`LeakOnDrop`].
+  #[inline]
+  pub unsafe fn from_slot(slot: S,) -> Self {
+    Self { slot: slot.into_raw(), _phantom: PhantomData, }
+  }
+  const_fn! {
+    /// Returns a raw pointer to the value without freeing the slot.
+    /// 
+    /// The slot can be recovered using [`Slot::from_raw`].
+    #[inline]
+    pub fn into_raw(self,) -> *mut T {
+      let slot = self.slot;
+      mem::forget(self,);
+
+      slot
+    }
+  }
+  /// Returns the slot.
+  #[inline]
+  pub fn into_slot(self,) -> S {
+    let slot = self.slot;
+    mem::forget(self,);
+
+    unsafe { S::from_raw(slot,) }
+  }
+}
+
+impl<'a, T, S,> Initialised<'a, T, S,>
+  where S: Slot<Target = T,>, {
+  const_fn! {
+    /// Moves the value behind the reference and leaves the reference uninitialised.
+    #[track_caller]
+    #[inline]
+    pub fn take(self,) -> (T, Uninitialised<'a, T, LeakOnDrop, S,>,) {
+      let slot = self.slot;
+      mem::forget(self,);
+
+      unsafe {
+        (
+          ptr::read(slot,),
+          Uninitialised {
+            slot,
+            origin: Origin::new::<T,>(),
+            _phantom: PhantomData,
+            _policy: PhantomData,
+          },
+        )
+      }
+    }
+  }
+  /// Copies the value out of the reference.
+  /// 
+  /// Reading a [`TriviallyReinit`] value leaves the slot initialised, so unlike
+  /// [`Initialised::take`] no [`Uninitialised`] has to be reinitialised or dropped; the handle
+  /// can be reassigned directly.
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut points = [(1, 2), (3, 4)];
+  /// Initialised::scope(&mut points[..], Abort, |init| {
+  ///   let elements = init.elements();
+  ///   let mut rejoin = elements.rejoin();
+  ///   for mut point in elements {
+  ///     let (x, y) = point.take_copy();
+  ///     *point = (y, x);
+  ///     rejoin.push(point);
+  ///   }
+  ///   (rejoin.finish(), ())
+  /// });
+  /// assert_eq!(points, [(2, 1), (4, 3)]);
+  /// ```
+  #[inline]
+  pub fn take_copy(&self,) -> T
+    where T: TriviallyReinit, { unsafe { ptr::read(self.slot,) } }
+  /// Moves the value out of the reference leaving a [`Stale`] handle.
+  /// 
+  /// Unlike the [`Uninitialised`] returned by [`Initialised::take`] the handle can be dropped
+  /// without reinitialising the slot as it still holds a valid copy of the value.
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut num = 1;
+  /// Initialised::scope(&mut num, Abort, |init| {
+  ///   let (v, stale) = init.take_trivial();
+  ///   if v > 0 { (stale.init(v * 10), ()) } else { (stale.restore(), ()) }
+  /// });
+  /// assert_eq!(num, 10);
+  /// ```
+  #[inline]
+  pub fn take_trivial(self,) -> (T, Stale<'a, T, S,>,)
+    where T: TriviallyReinit, {
+    let slot = self.into_raw();
+
+    (unsafe { ptr::read(slot,) }, Stale { slot, _phantom: PhantomData, },)
+  }
+  /// Drops the value in place and leaves the reference uninitialised.
+  /// 
+  /// Unlike [`Initialised::take`] the value is never moved; the process aborts if its
+  /// destructor panics as the slot cannot be refilled.
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut buffer = vec![0u8; 4096];
+  /// Initialised::scope(&mut buffer, Abort, |init| {
+  ///   (init.drop_value().init_with(|out| out.write(Vec::new())), ())
+  /// });
+  /// assert!(buffer.is_empty());
+  /// ```
+  #[track_caller]
+  #[inline]
+  pub fn drop_value(self,) -> Uninitialised<'a, T, LeakOnDrop, S,> {
+    let slot = self.into_raw();
+    let guard = Guard::new(slot, Abort,);
+    unsafe { ptr::drop_in_place(slot,) }
+    guard.forget();
+
+    Uninitialised {
+      slot,
+      origin: Origin::new::<T,>(),
+      _phantom: PhantomData,
+      _policy: PhantomData,
+    }
+  }
+  /// Replaces the value behind the reference with the result of passing it to `f`.
+  /// 
+  /// If `f` panics the reference is reinitialised with the value produced by `on_panic`
+  /// before unwinding continues.
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut v = vec![1, 2];
+  /// Initialised::scope(&mut v, Abort, |init| {
+  ///   (init.take_with(Abort, |mut v| { v.push(3); v }), ())
+  /// });
+  /// assert_eq!(v, [1, 2, 3]);
+  /// ```
+  pub fn take_with<P, F,>(self, on_panic: P, f: F,) -> Self
+    where P: OnPanic<T,>, F: FnOnce(T,) -> T, {
+    let slot = self.slot;
+    let guard = Guard::new(slot, on_panic,);
+    unsafe {
+      let value = f(ptr::read(slot,),);
+      guard.forget();
+      ptr::write(slot, value,);
+    }
+
+    self
+  }
+  /// Replaces the value behind the reference with the result of passing it to `f`, which
+  /// returns the original value along with an error on failure.
+  /// 
+  /// The reference is always reinitialised, with the original value if `f` fails or the value
+  /// produced by `on_panic` if `f` panics.
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut s = String::from("12");
+  /// let res = Initialised::scope(&mut s, Abort, |init| {
+  ///   init.try_replace_with(Abort, |s| match s.parse::<i32>() {
+  ///     Ok(n) if n > 100 => Ok(n.to_string()),
+  ///     _ => Err((s, "too small")),
+  ///   })
+  /// });
+  /// assert_eq!(res, Err("too small"));
+  /// assert_eq!(s, "12");
+  /// ```
+  pub fn try_replace_with<P, F, E,>(self, on_panic: P, f: F,) -> (Self, Result<(), E>,)
+    where P: OnPanic<T,>, F: FnOnce(T,) -> Result<T, (T, E,)>, {
+    let mut err = None;
+    let init = self.take_with(on_panic, |value,| match f(value,) {
+      Ok(value,) => value,
+      Err((value, e,),) => { err = Some(e,); value },
+    },);
+
+    (init, err.map_or(Ok(()), Err,),)
+  }
+}
+
+impl<T: ?Sized, S,> Drop for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, {
+  #[inline]
+  fn drop(&mut self,) { drop(unsafe { S::from_raw(self.slot,) },) }
+}
+
+unsafe impl<T: ?Sized, S,> Send for Initialised<'_, T, S,>
+  where S: Slot<Target = T,> + Send, {}
+
+unsafe impl<T: ?Sized, S,> Sync for Initialised<'_, T, S,>
+  where S: Slot<Target = T,> + Sync, {}
+
+impl<T: ?Sized, S,> Deref for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, {
+  type Target = T;
+
+  #[inline]
+  fn deref(&self,) -> &Self::Target { unsafe { &*self.slot } }
+}
+
+impl<T: ?Sized, S,> DerefMut for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, {
+  #[inline]
+  fn deref_mut(&mut self,) -> &mut Self::Target { unsafe { &mut *self.slot } }
+}
+
+impl<T: ?Sized, S,> fmt::Debug for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, T: fmt::Debug, {
+  #[inline]
+  fn fmt(&self, fmt: &mut fmt::Formatter,) -> fmt::Result { fmt::Debug::fmt(&**self, fmt,) }
+}
+
+impl<T: ?Sized, S,> fmt::Display for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, T: fmt::Display, {
+  #[inline]
+  fn fmt(&self, fmt: &mut fmt::Formatter,) -> fmt::Result { fmt::Display::fmt(&**self, fmt,) }
+}
+
+impl<T: ?Sized, U: ?Sized, S, R,> PartialEq<Initialised<'_, U, R,>> for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, R: Slot<Target = U,>, T: PartialEq<U>, {
+  #[inline]
+  fn eq(&self, rhs: &Initialised<'_, U, R,>,) -> bool { **self == **rhs }
+}
+
+impl<T: ?Sized, S,> Eq for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, T: Eq, {}
+
+impl<T: ?Sized, U: ?Sized, S, R,> PartialOrd<Initialised<'_, U, R,>> for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, R: Slot<Target = U,>, T: PartialOrd<U>, {
+  #[inline]
+  fn partial_cmp(&self, rhs: &Initialised<'_, U, R,>,) -> Option<Ordering> {
+    (**self).partial_cmp(&**rhs,)
+  }
+}
+
+impl<T: ?Sized, S,> Ord for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, T: Ord, {
+  #[inline]
+  fn cmp(&self, rhs: &Self,) -> Ordering { (**self).cmp(&**rhs,) }
+}
+
+impl<T: ?Sized, S,> Hash for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, T: Hash, {
+  #[inline]
+  fn hash<H,>(&self, state: &mut H,)
+    where H: Hasher, { (**self).hash(state,) }
+}
+
+impl<T: ?Sized, S,> AsRef<T> for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, {
+  #[inline]
+  fn as_ref(&self,) -> &T { self }
+}
+
+impl<T: ?Sized, S,> AsMut<T> for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, {
+  #[inline]
+  fn as_mut(&mut self,) -> &mut T { self }
+}
+
+impl<T: ?Sized, S,> Borrow<T> for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, {
+  #[inline]
+  fn borrow(&self,) -> &T { self }
+}
+
+impl<T: ?Sized, S,> BorrowMut<T> for Initialised<'_, T, S,>
+  where S: Slot<Target = T,>, {
+  #[inline]
+  fn borrow_mut(&mut self,) -> &mut T { self }
+}
+
+impl<I: ?Sized, S,> Iterator for Initialised<'_, I, S,>
+  where S: Slot<Target = I,>, I: Iterator, {
+  type Item = I::Item;
+
+  #[inline]
+  fn next(&mut self,) -> Option<Self::Item> { (**self).next() }
+  #[inline]
+  fn size_hint(&self,) -> (usize, Option<usize>,) { (**self).size_hint() }
+}
+
+/// Types whose fields can be projected out of an [`Initialised`].
+/// 
+/// This should be implemented using `#[derive(Reinit)]` which requires the `derive` feature.
+/// 
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use reinit::*;
+/// 
+/// #[derive(Reinit)]
+/// struct Pair {
+///   name: String,
+///   count: usize,
+/// }
+/// 
+/// let mut pair = Pair { name: String::from("hello"), count: 1 };
+/// Initialised::scope(&mut pair, Abort, |init| {
+///   let (name, fields) = init.project().take_name();
+///   let fields = fields.init_name(name + " world");
+///   (fields.join(), ())
+/// });
+/// assert_eq!(pair.name, "hello world");
+/// # }
+/// ```
+/// 
+/// # Safety
+/// 
+/// The fields must only [`Join`] into an `Initialised` for the slot which was projected and
+/// only once every field is initialised; this includes fields from [`Reinit::project_uninit`].
+pub unsafe trait Reinit: Sized {
+  /// The per-field handles.
+  type Fields<'a,>: Join<Joined = Initialised<'a, Self,>,>
+    where Self: 'a;
+  /// The per-field handles with every field uninitialised.
+  type UninitFields<'a,>
+    where Self: 'a;
+
+  /// Projects `init` into per-field handles.
+  fn project<'a,>(init: Initialised<'a, Self,>,) -> Self::Fields<'a,>;
+  /// Projects `uninit` into per-field uninitialised handles.
+  fn project_uninit<'a, P,>(uninit: Uninitialised<'a, Self, P,>,) -> Self::UninitFields<'a,>
+    where P: DropPolicy<Self,>;
+}
+
+/// Handles which can be rejoined into a handle for the value they were split from.
+pub trait Join {
+  /// The rejoined handle.
+  type Joined;
+
+  /// Rejoins the handles.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if the handles were not all split from the same value.
+  #[track_caller]
+  fn join(self,) -> Self::Joined;
+}
+
+/// The slot which handles were split from.
+/// 
+/// Split handles are rejoined through their `Base` so only the parts of the slot which was
+/// split can be rejoined and the rejoined handle references the whole slot.
+#[must_use]
+pub struct Base<'a, T: 'a + ?Sized,> {
+  /// The slot.
+  slot: *mut T,
+  _phantom: PhantomData<&'a mut T>,
+}
+
+impl<'a, T: ?Sized,> Base<'a, T,> {
+  /// Consumes `init` so that it can be split.
+  #[doc(hidden,)]
+  #[inline]
+  pub fn new(init: Initialised<'a, T,>,) -> Self {
+    Self { slot: init.into_inner(), _phantom: PhantomData, }
+  }
+  /// Consumes `uninit` so that it can be split.
+  #[doc(hidden,)]
+  #[inline]
+  pub fn from_uninit<P,>(uninit: Uninitialised<'a, T, P,>,) -> Self
+    where P: DropRefill<T,>, {
+    Self { slot: uninit.into_raw(), _phantom: PhantomData, }
+  }
+  /// Returns a pointer to the slot.
+  #[inline]
+  pub fn as_ptr(&self,) -> *mut T { self.slot }
+  /// Returns a handle to the slot.
+  /// 
+  /// # Safety
+  /// 
+  /// Every part of the slot must be initialised.
+  #[doc(hidden,)]
+  #[inline]
+  pub unsafe fn into_initialised(self,) -> Initialised<'a, T,> {
+    Initialised::new(&mut *self.slot,)
+  }
+  /// Returns an uninitialised handle to the slot.
+  #[track_caller]
+  #[inline]
+  pub(crate) fn into_uninitialised<P,>(self,) -> Uninitialised<'a, T, P,>
+    where P: DropRefill<T,>, {
+    Uninitialised {
+      slot: self.slot,
+      origin: Origin::new::<T,>(),
+      _phantom: PhantomData,
+      _policy: PhantomData,
+    }
+  }
+}
+
+impl<T: ?Sized,> fmt::Debug for Base<'_, T,> {
+  fn fmt(&self, fmt: &mut fmt::Formatter,) -> fmt::Result {
+    fmt.debug_tuple(stringify!(Base),).field(&self.slot,).finish()
+  }
+}
+
+impl<'a, T,> Initialised<'a, T,>
+  where T: Reinit, {
+  /// Projects the value into per-field handles.
+  #[inline]
+  pub fn project(self,) -> T::Fields<'a,> { T::project(self,) }
+}
+
+impl<'a, T, P,> Uninitialised<'a, T, P,>
+  where T: Reinit, P: DropPolicy<T,>, {
+  /// Projects the slot into per-field handles so that it can be initialised in place one field
+  /// at a time.
+  /// 
+  /// ```
+  /// # #[cfg(feature = "derive")] {
+  /// use reinit::*;
+  /// 
+  /// #[derive(Reinit)]
+  /// struct Pair {
+  ///   name: String,
+  ///   count: usize,
+  /// }
+  /// 
+  /// let mut pair = Pair { name: String::from("hello"), count: 1 };
+  /// Initialised::scope(&mut pair, Abort, |init| {
+  ///   let uninit = init.drop_value();
+  ///   let fields = uninit.project().init_name(String::from("world")).init_count(2);
+  ///   (fields.join(), ())
+  /// });
+  /// assert_eq!((&*pair.name, pair.count), ("world", 2));
+  /// # }
+  /// ```
+  #[inline]
+  pub fn project(self,) -> T::UninitFields<'a,> { T::project_uninit(self,) }
+}
+
+/// Types whose values remain valid in their slot after being moved out.
+/// 
+/// This is implemented for every `Copy` type and can be implemented for plain data types which
+/// do not implement `Copy`.
+/// 
+/// ```
+/// use reinit::*;
+/// 
+/// struct Pixel { r: u8, g: u8, b: u8 }
+/// 
+/// // SAFETY: `Pixel` is plain data without a destructor.
+/// unsafe impl TriviallyReinit for Pixel {}
+/// ```
+/// 
+/// # Safety
+/// 
+/// The type must not have drop glue and a bitwise copy of a value must be a valid value which
+/// can be used independently of the original.
+pub unsafe trait TriviallyReinit {}
+
+unsafe impl<T,> TriviallyReinit for T
+  where T: Copy, {}
+
+/// A reference whose [`TriviallyReinit`] value has been moved out, see
+/// [`Initialised::take_trivial`].
+/// 
+/// The slot still holds a valid copy of the value so dropping this leaves the slot unchanged.
+#[must_use]
+pub struct Stale<'a, T: 'a, S = &'a mut T,>
+  where T: TriviallyReinit, S: Slot<Target = T,>, {
+  /// The reference.
+  slot: *mut T,
+  _phantom: PhantomData<(&'a (), S,)>,
+}
+
+impl<'a, T, S,> Stale<'a, T, S,>
+  where T: TriviallyReinit, S: Slot<Target = T,>, {
+  /// Reinitialises the reference.
+  #[inline]
+  pub fn init(self, value: T,) -> Initialised<'a, T, S,> {
+    let slot = mem::ManuallyDrop::new(self,).slot;
+    unsafe { ptr::write(slot, value,) }
+
+    Initialised { slot, _phantom: PhantomData, }
+  }
+  /// Keeps the copy of the value which was taken.
+  #[inline]
+  pub fn restore(self,) -> Initialised<'a, T, S,> {
+    let slot = mem::ManuallyDrop::new(self,).slot;
+
+    Initialised { slot, _phantom: PhantomData, }
+  }
+  /// Converts the handle into an [`Uninitialised`] which must be reinitialised.
+  #[track_caller]
+  #[inline]
+  pub fn into_uninitialised(self,) -> Uninitialised<'a, T, LeakOnDrop, S,> {
+    let slot = mem::ManuallyDrop::new(self,).slot;
+    let origin = Origin::new::<T,>();
+
+    Uninitialised { slot, origin, _phantom: PhantomData, _policy: PhantomData, }
+  }
+}
+
+impl<T, S,> fmt::Debug for Stale<'_, T, S,>
+  where T: TriviallyReinit, S: Slot<Target = T,>, {
+  fn fmt(&self, fmt: &mut fmt::Formatter,) -> fmt::Result {
+    fmt.debug_tuple(stringify!(Stale),).field(&self.slot,).finish()
+  }
+}
+
+impl<T, S,> Drop for Stale<'_, T, S,>
+  where T: TriviallyReinit, S: Slot<Target = T,>, {
+  #[inline]
+  fn drop(&mut self,) { drop(unsafe { S::from_raw(self.slot,) },) }
+}
+
+/// State machines which can transition between states behind an [`Initialised`].
+/// 
+/// This should be implemented using `#[derive(Transition)]` which requires the `derive`
+/// feature.
+/// 
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use reinit::*;
+/// 
+/// #[derive(Transition, Debug, PartialEq)]
+/// enum Connection {
+///   #[transition(fallback)]
+///   Closed,
+///   Connecting { attempts: u32 },
+///   Open(Vec<u8>),
+/// }
+/// 
+/// let mut conn = Connection::Connecting { attempts: 1 };
+/// Initialised::scope(&mut conn, Abort, |init| {
+///   (init.transition(|conn| match conn {
+///     Connection::Connecting { .. } => Connection::Open(Vec::new()),
+///     other => other,
+///   }), ())
+/// });
+/// assert_eq!(conn, Connection::Open(Vec::new()));
+/// # }
+/// ```
+pub trait Transition: Sized {
+  /// The state written if a transition panics.
+  fn fallback() -> Self;
+}
+
+impl<'a, T, S,> Initialised<'a, T, S,>
+  where T: Transition, S: Slot<Target = T,>, {
+  /// Moves the current state into `f` and writes back the state it returns.
+  /// 
+  /// If `f` panics [`Transition::fallback`] is written before unwinding continues.
+  #[inline]
+  pub fn transition<F,>(self, f: F,) -> Self
+    where F: FnOnce(T,) -> T, { self.take_with(T::fallback, f,) }
+}
+
+/// A reference to uninitialised memory.
+/// 
+/// Dropping this value invokes its [`DropPolicy`], by default [`LeakOnDrop`] which leaves the
+/// slot for [`Initialised::scope`] to refill. A dropped handle cannot be reinitialised so the
+/// closure passed to `scope` fails to compile unless it reinitialises the slot another way:
+/// 
+/// ```compile_fail
+/// use reinit::*;
+/// 
+/// let mut num = 42;
+/// Initialised::scope(&mut num, Abort, |init| {
+///   let (v, uninit) = init.take();
+///   drop(uninit);
+///   (init, ())
+/// });
+/// ```
+/// 
+/// `T` may be a slice in which case each element is reinitialised using the policy.
+#[cfg_attr(not(feature = "debug-location",), repr(transparent,),)]
+#[must_use]
+pub struct Uninitialised<'a, T: 'a + ?Sized, P = LeakOnDrop, S = &'a mut T,>
+  where P: DropRefill<T,>, S: Slot<Target = T,>, {
+  /// The reference.
+  slot: *mut T,
+  /// Where the reference was uninitialised.
+  origin: Origin,
+  _phantom: PhantomData<(&'a (), S,)>,
+  _policy: PhantomData<fn() -> P>,
+}
+
+impl<'a, T, P, S,> Uninitialised<'a, T, P, S,>
+  where P: DropPolicy<T,>, S: Slot<Target = T,>, {
+  const_fn! {
+    /// Reinitialises the reference.
+    #[cfg_attr(feature = "nightly", allow(clippy::incompatible_msrv,),)]
+    #[inline]
+    pub fn init(self, value: T,) -> Initialised<'a, T, S,> {
+      use core::mem::MaybeUninit;
+
+      unsafe {
+        let this = MaybeUninit::new(self,);
+        let slot = (*this.as_ptr()).slot;
+        ptr::write(slot, value,);
+        Initialised { slot, _phantom: PhantomData, }
+      }
+    }
+  }
+  /// Reinitialises the reference in place using `f` which must return the reference produced
+  /// by writing to its argument.
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut buffer = [0u8; 4096];
+  /// Initialised::scope(&mut buffer, Abort, |init| {
+  ///   let (_, uninit) = init.take();
+  ///   let init = uninit.init_with(|out| {
+  ///     let bytes = out.as_mut_ptr() as *mut u8;
+  ///     // SAFETY: Every byte is written before the array is assumed initialised.
+  ///     unsafe {
+  ///       for index in 0..4096 { bytes.add(index).write(1) }
+  ///       out.assume_init_mut()
+  ///     }
+  ///   });
+  ///   (init, ())
+  /// });
+  /// assert!(buffer.iter().all(|&b| b == 1));
+  /// ```
+  /// 
+  /// Arrays can also be filled without `unsafe` by converting them to an `Uninitialised<[T]>`,
+  /// see [`Uninitialised::init_from_fn`].
+  /// 
+  /// The handle is disarmed before `f` is called so if `f` panics the drop policy is not
+  /// invoked; anything written to the slot is leaked and the slot is left for
+  /// [`Initialised::scope`] to refill.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `f` returns a reference to a different value, leaving the slot as above.
+  #[track_caller]
+  #[inline]
+  pub fn init_with<F,>(self, f: F,) -> Initialised<'a, T, S,>
+    where F: FnOnce(&mut mem::MaybeUninit<T>,) -> &mut T, {
+    let slot = self.into_raw();
+    let init = f(unsafe { &mut *(slot as *mut mem::MaybeUninit<T>) },) as *mut T;
+    if !ptr::eq(init, slot,) {
+      panic!(concat!("`", stringify!(init_with), "` returned a reference to a different value",),)
+    }
+
+    Initialised { slot, _phantom: PhantomData, }
+  }
+  /// Reinitialises the reference with the new value or, on failure, the original value
+  /// returning the error.
+  #[inline]
+  pub fn init_or_restore<E,>(
+    self,
+    result: Result<T, (T, E,)>,
+  ) -> (Initialised<'a, T, S,>, Result<(), E>,) {
+    match result {
+      Ok(value,) => (self.init(value,), Ok(()),),
+      Err((value, e,),) => (self.init(value,), Err(e,),),
+    }
+  }
+  /// Returns the slot as a `MaybeUninit` so it can be written through an out parameter.
+  /// 
+  /// ```
+  /// use core::mem::MaybeUninit;
+  /// use reinit::*;
+  /// 
+  /// fn answer(out: &mut MaybeUninit<i32>) { out.write(42); }
+  /// 
+  /// let mut num = 1;
+  /// Initialised::scope(&mut num, Abort, |init| {
+  ///   let (_, mut uninit) = init.take();
+  ///   answer(uninit.as_out());
+  ///   // SAFETY: `answer` initialised the slot.
+  ///   (unsafe { uninit.assume_init() }, ())
+  /// });
+  /// assert_eq!(num, 42);
+  /// ```
+  #[inline]
+  pub fn as_out(&mut self,) -> &mut mem::MaybeUninit<T> {
+    unsafe { &mut *(self.slot as *mut mem::MaybeUninit<T>) }
+  }
+}
+
+impl<'a, T: ?Sized, P, S,> Uninitialised<'a, T, P, S,>
+  where P: DropRefill<T,>, S: Slot<Target = T,>, {
+  const_fn! {
+    /// Asserts that the slot has been initialised, such as through [`Uninitialised::as_out`].
+    /// 
+    /// # Safety
+    /// 
+    /// The slot must hold a valid `T`.
+    #[inline]
+    pub unsafe fn assume_init(self,) -> Initialised<'a, T, S,> {
+      let slot = self.slot;
+      mem::forget(self,);
+
+      Initialised { slot, _phantom: PhantomData, }
+    }
+  }
+  const_fn! {
+    /// Returns a raw pointer to the uninitialised slot without invoking the drop policy.
+    /// 
+    /// The caller becomes responsible for reinitialising the slot.
+    #[inline]
+    pub fn into_raw(self,) -> *mut T {
+      let slot = self.slot;
+      mem::forget(self,);
+
+      slot
+    }
+  }
+  /// Changes the policy used if this value is dropped.
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut num = 42;
+  /// // SAFETY: `uninit` is dropped rather than leaked so `num` is reinitialised.
+  /// let (v, uninit) = unsafe { Initialised::new(&mut num) }.take();
+  /// drop(uninit.with_policy::<DefaultOnDrop>());
+  /// assert_eq!(num, 0);
+  /// ```
+  #[inline]
+  pub fn with_policy<Q,>(self,) -> Uninitialised<'a, T, Q, S,>
+    where Q: DropRefill<T,>, {
+    let Self { slot, origin, .. } = *mem::ManuallyDrop::new(self,);
+
+    Uninitialised { slot, origin, _phantom: PhantomData, _policy: PhantomData, }
+  }
+}
+
+impl<'a, T, P,> Uninitialised<'a, T, P,>
+  where P: DropPolicy<T,>, {
+  /// Reuses the slot for a `U` with the same size and alignment as `T`.
+  /// 
+  /// Fails to compile if the layouts differ, see [`Uninitialised::try_cast`] for a runtime
+  /// check. The slot must be cast back to a `T` before it can be rejoined.
+  /// 
+  /// ```
+  /// use reinit::*;
+  /// 
+  /// let mut num = 3u32;
+  /// Initialised::scope(&mut num, Abort, |init| {
+  ///   let (v, uninit) = init.take();
+  ///   let (f, uninit) = uninit.cast::<f32>().init(v as f32 / 2.0).take();
+  ///   (uninit.cast::<u32>().init(f.to_bits()), ())
+  /// });
+  /// assert_eq!(num, 1.5f32.to_bits());
+  /// ```
+  /// 
+  /// ```compile_fail
+  /// # use reinit::*;
+  /// let mut num = 3u32;
+  /// let (_, uninit) = unsafe { Initialised::new(&mut num) }.take();
+  /// let _ = uninit.cast::<u64>();
+  /// ```
+  #[inline]
+  pub fn cast<U,>(self,) -> Uninitialised<'a, U,> {
+    const { assert!(same_layout::<T, U,>(), "`cast` requires types with the same layout",) }
+
+    self.cast_unchecked()
+  }
+  /// Reuses the slot for a `U` if it has the same size and alignment as `T`, otherwise returns
+  /// `self`.
+  #[inline]
+  pub fn try_cast<U,>(self,) -> Result<Uninitialised<'a, U,>, Self> {
+    if same_layout::<T, U,>() { Ok(self.cast_unchecked(),) } else { Err(self,) }
+  }
+  /// Reuses the slot for a `U` without checking the layout.
+  #[inline]
+  fn cast_unchecked<U,>(self,) -> Uninitialised<'a, U,> {
+    let Self { slot, origin, .. } = *mem::ManuallyDrop::new(self,);
+
+    Uninitialised { slot: slot as *mut U, origin, _phantom: PhantomData, _policy: PhantomData, }
+  }
+}
 
-use core::{
-  ops::{Deref, DerefMut,},
-  marker::PhantomData,
-};
+/// Returns `true` if `T` and `U` have the same size and alignment.
+#[inline]
+const fn same_layout<T, U,>() -> bool {
+  mem::size_of::<T>() == mem::size_of::<U>() && mem::align_of::<T>() == mem::align_of::<U>()
+}
 
-/// A reference to initialised memory.
-#[repr(transparent,)]
-pub struct Initialised<'a, T: 'a,> {
-  /// The reference.
-  slot: &'a mut T,
+impl<'a, T,> Uninitialised<'a, T, LeakOnDrop, MaybeUninitSlot<'a, T,>,> {
+  const_fn! {
+    /// Constructs an `Uninitialised` for `slot`.
+    /// 
+    /// `slot` need not be initialised again so leaking the result is safe. The handle
+    /// references `slot` as a [`MaybeUninitSlot`] so it cannot be passed off as a handle
+    /// from [`Initialised::scope`].
+    #[cfg_attr(feature = "nightly", allow(clippy::incompatible_msrv,),)]
+    #[track_caller]
+    #[inline]
+    pub fn from_maybe_uninit(slot: &'a mut mem::MaybeUninit<T>,) -> Self {
+      Self {
+        slot: slot.as_mut_ptr(),
+        origin: Origin::new::<T,>(),
+        _phantom: PhantomData,
+        _policy: PhantomData,
+      }
+    }
+  }
 }
 
-impl<'a, T,> Initialised<'a, T,> {
-  /// Constructs a new `Initialised` from `slot`.
-  #[inline]
-  pub const fn new(slot: &'a mut T,) -> Self { Self { slot, } }
-  /// Returns the inner value.
+impl<'a, T: ?Sized,> Uninitialised<'a, T,>
+  where LeakOnDrop: DropRefill<T,>, {
+  const_fn! {
+    /// Constructs an `Uninitialised` from a raw pointer such as an FFI out parameter.
+    /// 
+    /// ```
+    /// use reinit::*;
+    /// 
+    /// /// # Safety
+    /// /// 
+    /// /// `out` must be valid for writes and not otherwise accessed during the call.
+    /// unsafe extern "C" fn answer(out: *mut i32) {
+    ///   let uninit = unsafe { Uninitialised::from_raw(out) };
+    ///   uninit.init(42).into_raw();
+    /// }
+    /// 
+    /// let mut num = 0;
+    /// // SAFETY: `num` is a valid, unaliased out parameter.
+    /// unsafe { answer(&mut num) };
+    /// assert_eq!(num, 42);
+    /// ```
+    /// 
+    /// # Safety
+    /// 
+    /// `raw` must be valid for reads and writes of a `T` and not otherwise accessed for `'a`.
+    /// Any value in the slot is overwritten without being dropped and the slot is left
+    /// uninitialised if the result is leaked, dropped or converted using
+    /// [`Uninitialised::into_raw`].
+    #[track_caller]
+    #[inline]
+    pub unsafe fn from_raw(raw: *mut T,) -> Self {
+      Self { slot: raw, origin: Origin::new::<T,>(), _phantom: PhantomData, _policy: PhantomData, }
+    }
+  }
+  /// Recombines uninitialised handles split from an `Initialised`.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if the handles were not all split from the same value.
+  #[track_caller]
   #[inline]
-  pub const fn into_inner(self,) -> &'a mut T { self.slot }
-  /// Moves the value behind the reference and leaves the reference uninitialised.
+  pub fn join<J,>(parts: J,) -> Self
+    where J: Join<Joined = Self,>, { parts.join() }
+}
+
+impl<'a, T: ?Sized, P, S,> Drop for Uninitialised<'a, T, P, S,>
+  where P: DropRefill<T,>, S: Slot<Target = T,>, {
+  #[track_caller]
   #[inline]
-  pub const fn take(self,) -> (T, Uninitialised<'a, T,>,) {
-    use core::ptr;
+  fn drop(&mut self,) {
+    //The slot is leaked along with the value.
+    if !P::REFILLS { return }
 
     unsafe {
-      (
-        ptr::read(self.slot,),
-        Uninitialised { slot: self.slot, _phantom: PhantomData, },
-      )
+      P::refill(self.slot, &self.origin,);
+      drop(S::from_raw(self.slot,),)
     }
   }
 }
 
-impl<T,> Deref for Initialised<'_, T,>
-  where T: Deref, {
-  type Target = T::Target;
+/// Describes where an [`Uninitialised`] was created.
+/// 
+/// Nothing is recorded unless the `debug-location` feature is enabled.
+#[derive(Clone, Copy, Debug,)]
+pub struct Origin {
+  /// Where [`Initialised::take`] was called.
+  #[cfg(feature = "debug-location",)]
+  location: &'static core::panic::Location<'static>,
+  /// The name of the type which was taken.
+  #[cfg(feature = "debug-location",)]
+  type_name: &'static str,
+}
 
+impl Origin {
+  const_fn! {
+    /// Records the caller taking a `T`.
+    #[track_caller]
+    #[inline]
+    fn new<T: ?Sized,>() -> Self {
+      Self {
+        #[cfg(feature = "debug-location",)]
+        location: core::panic::Location::caller(),
+        #[cfg(feature = "debug-location",)]
+        type_name: core::any::type_name::<T,>(),
+      }
+    }
+  }
+  /// Where the value was taken, if recorded.
   #[inline]
-  fn deref(&self,) -> &Self::Target { self.slot.deref() }
+  pub fn location(&self,) -> Option<&'static core::panic::Location<'static>> {
+    #[cfg(feature = "debug-location",)]
+    { Some(self.location) }
+    #[cfg(not(feature = "debug-location",),)]
+    { None }
+  }
+  /// The name of the type which was taken, if recorded.
+  #[inline]
+  pub fn type_name(&self,) -> Option<&'static str> {
+    #[cfg(feature = "debug-location",)]
+    { Some(self.type_name) }
+    #[cfg(not(feature = "debug-location",),)]
+    { None }
+  }
+}
+
+impl fmt::Display for Origin {
+  fn fmt(&self, fmt: &mut fmt::Formatter,) -> fmt::Result {
+    if let Some(type_name,) = self.type_name() { write!(fmt, " of type `{}`", type_name,)? }
+    if let Some(location,) = self.location() { write!(fmt, " taken at {}", location,)? }
+
+    Ok(())
+  }
+}
+
+/// Determines what happens when an [`Uninitialised`] is dropped.
+/// 
+/// The slot must be reinitialised before the borrow ends so the policy must either produce a
+/// replacement value or diverge.
+pub trait DropPolicy<T,> {
+  /// `false` if the handle is leaked rather than refilled, see [`LeakOnDrop`].
+  const REFILLS: bool = true;
+
+  /// Produces the value to reinitialise the slot with.
+  /// 
+  /// `origin` describes where the value was taken.
+  #[track_caller]
+  fn on_drop(origin: &Origin,) -> T;
 }
 
-impl<T,> DerefMut for Initialised<'_, T,>
-  where T: DerefMut, {
+/// Reinitialises the slot of a dropped [`Uninitialised`].
+/// 
+/// This is implemented for every [`DropPolicy`] and, for slices, every [`DropPolicy`] of the
+/// element type which is used to reinitialise each element.
+/// 
+/// # Safety
+/// 
+/// Unless `REFILLS` is `false`, `refill` must initialise the slot or diverge.
+pub unsafe trait DropRefill<T: ?Sized,> {
+  /// `false` if the handle is leaked rather than refilled, see [`LeakOnDrop`].
+  const REFILLS: bool = true;
+
+  /// Overwrites `slot` without dropping its previous contents.
+  /// 
+  /// # Safety
+  /// 
+  /// `slot` must be valid for writes.
+  #[track_caller]
+  unsafe fn refill(slot: *mut T, origin: &Origin,);
+}
+
+unsafe impl<T, P,> DropRefill<T,> for P
+  where P: DropPolicy<T,>, {
+  const REFILLS: bool = P::REFILLS;
+
+  #[track_caller]
   #[inline]
-  fn deref_mut(&mut self,) -> &mut Self::Target { self.slot.deref_mut() }
+  unsafe fn refill(slot: *mut T, origin: &Origin,) { ptr::write(slot, P::on_drop(origin,),) }
 }
 
-impl<'a, T,> From<&'a mut T> for Initialised<'a, T,> {
+unsafe impl<T, P,> DropRefill<[T],> for P
+  where P: DropPolicy<T,>, {
+  const REFILLS: bool = P::REFILLS;
+
+  #[track_caller]
   #[inline]
-  fn from(from: &'a mut T,) -> Self { Initialised::new(from,) }
+  unsafe fn refill(slot: *mut [T], origin: &Origin,) {
+    let elements = slot as *mut T;
+    for index in 0..slot.len() { ptr::write(elements.add(index,), P::on_drop(origin,),) }
+  }
 }
 
-/// A reference to uninitialised memory.
+/// Leaves the slot uninitialised when an [`Uninitialised`] is dropped, as if it were leaked.
 /// 
-/// Dropping this value will panic as the referenced memory is left uninitialised.
+/// This is the default policy. The guard of the enclosing [`Initialised::scope`] refills the
+/// slot so a handle dropped while unwinding does not abort the process.
 /// 
-/// ```no_run
+/// ```
+/// use std::panic;
 /// use reinit::*;
 /// 
-/// let mut num = 42;
-/// let init = Initialised::new(&mut num);
-/// let (v, uninit) = init.take();
-/// // This will panic when `uninit` is dropped because the value in `num` was moved.
+/// let mut s = String::from("42");
+/// let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+///   Initialised::scope(&mut s, RestoreDefault, |init| {
+///     let (s, uninit) = init.take();
+///     let n = s.parse::<u8>().unwrap() * 10;
+///     (uninit.init(n.to_string()), ())
+///   })
+/// }));
+/// assert!(res.is_err());
+/// assert_eq!(s, "");
 /// ```
-#[repr(transparent,)]
-#[must_use]
-pub struct Uninitialised<'a, T: 'a,> {
-  /// The reference.
-  #[allow(dead_code,)]
-  slot: *mut T,
-  _phantom: PhantomData<&'a ()>,
+#[derive(Clone, Copy, Debug, Default,)]
+pub struct LeakOnDrop;
+
+impl<T,> DropPolicy<T,> for LeakOnDrop {
+  const REFILLS: bool = false;
+
+  #[inline]
+  fn on_drop(_: &Origin,) -> T {
+    panic!(concat!("`", stringify!(LeakOnDrop), "` does not produce a value",),)
+  }
 }
 
-impl<'a, T,> Uninitialised<'a, T,> {
-  /// Reinitialises the reference.
+/// Panics when an [`Uninitialised`] is dropped.
+/// 
+/// A handle dropped while unwinding panics again which aborts the process.
+#[derive(Clone, Copy, Debug, Default,)]
+pub struct PanicOnDrop;
+
+impl<T,> DropPolicy<T,> for PanicOnDrop {
+  #[track_caller]
   #[inline]
-  pub const fn init(self, value: T,) -> Initialised<'a, T,> {
-    use core::{ptr, mem::{transmute, MaybeUninit,},};
+  fn on_drop(origin: &Origin,) -> T {
+    panic!(concat!("Dropped an `", stringify!(Uninitialised),"` value{}",), origin,)
+  }
+}
 
-    unsafe {
-      let slot = transmute::<_, *mut T,>(MaybeUninit::new(self,),);
-      ptr::write(slot, value,);
-      Initialised::new(&mut *slot,)
-    }
+/// Aborts the process when an [`Uninitialised`] is dropped.
+/// 
+/// Useful where unwinding is unavailable or undesirable.
+#[derive(Clone, Copy, Debug, Default,)]
+pub struct AbortOnDrop;
+
+impl<T,> DropPolicy<T,> for AbortOnDrop {
+  #[inline]
+  fn on_drop(origin: &Origin,) -> T {
+    abort(format_args!(concat!("Dropped an `", stringify!(Uninitialised),"` value{}",), origin,),)
+  }
+}
+
+/// Writes `T::default()` back into the slot when an [`Uninitialised`] is dropped.
+#[derive(Clone, Copy, Debug, Default,)]
+pub struct DefaultOnDrop;
+
+impl<T,> DropPolicy<T,> for DefaultOnDrop
+  where T: Default, {
+  #[inline]
+  fn on_drop(_: &Origin,) -> T { T::default() }
+}
+
+/// Panics if a handle being rejoined references `slot` rather than `expected`.
+#[track_caller]
+#[inline]
+fn check_slot<T: ?Sized,>(slot: *const T, expected: *const T,) {
+  if !ptr::eq(slot, expected,) {
+    panic!("Rejoined a handle from a different slot",)
   }
 }
 
-impl<'a, T,> Drop for Uninitialised<'a, T,> {
+#[doc(hidden,)]
+pub mod __derive {
+  //! Support for `reinit-derive`.
+
+  use super::*;
+
+  /// Panics if `init` does not reference `field`.
   #[track_caller]
   #[inline]
-  fn drop(&mut self,) { panic!(concat!("Dropped an `", stringify!(Uninitialised),"` value",),) }
+  pub fn check<T,>(init: &Initialised<T,>, field: *mut T,) { check_slot(&**init, field,) }
 }
 
 #[cfg(test,)]
 mod tests {
   use super::*;
 
+  extern crate std;
+
+  use std::panic::{self, AssertUnwindSafe,};
+
+  #[cfg(not(feature = "debug-location",),)]
   #[test]
   fn test_sizes() {
     assert_eq!(core::mem::size_of::<Initialised<bool,>>(), core::mem::size_of::<*mut bool>());
@@ -127,20 +1401,808 @@ mod tests {
   }
   #[test]
   #[should_panic]
-  #[allow(unused_must_use,)]
   fn test_dropping_uninit() {
     let mut b = true;
-    let init = Initialised::new(&mut b,);
-    init.take();
+    let (_, uninit,) = unsafe { Initialised::new(&mut b,) }.take();
+    drop(uninit.with_policy::<PanicOnDrop>(),);
   }
   #[test]
-  fn test_reinit() {
+  fn test_default_on_drop() {
     let mut b = 42;
-    let init = Initialised::new(&mut b,);
-    let (v, uninit,) = init.take();
+    let (v, uninit,) = unsafe { Initialised::new(&mut b,) }.take();
     assert_eq!(v, 42, "Got incorrect value",);
-    let v = *uninit.init(10,).into_inner();
-    assert_eq!(v, b, "Ptr does not line up",);
+    drop(uninit.with_policy::<DefaultOnDrop>(),);
+    assert_eq!(b, 0, "Did not restore the default",);
+  }
+  #[test]
+  fn test_custom_policy() {
+    struct Seven;
+
+    impl DropPolicy<i32,> for Seven {
+      fn on_drop(_: &Origin,) -> i32 { 7 }
+    }
+
+    let mut b = 42;
+    let (_, uninit,) = unsafe { Initialised::new(&mut b,) }.take();
+    drop(uninit.with_policy::<Seven>(),);
+    assert_eq!(b, 7, "Did not use the policy",);
+  }
+  #[cfg(feature = "debug-location",)]
+  #[test]
+  fn test_origin() {
+    let mut b = 42;
+    let line = line!() + 1;
+    let (_, uninit,) = unsafe { Initialised::new(&mut b,) }.take();
+    assert_eq!(uninit.origin.type_name(), Some("i32"), "Recorded the wrong type",);
+    let location = uninit.origin.location().map(|loc,| loc.line(),);
+    assert_eq!(location, Some(line), "Recorded the wrong line",);
+
+    let uninit = uninit.with_policy::<PanicOnDrop>();
+    let msg = panic::catch_unwind(AssertUnwindSafe(move || drop(uninit,),),).unwrap_err();
+    let msg = msg.downcast::<std::string::String>().expect("Panicked with an unexpected payload",);
+    assert!(msg.contains("`i32`") && msg.contains(file!()), "Message missing the origin: {}", msg,);
+  }
+  #[test]
+  fn test_reinit() {
+    let mut b = 42;
+    let ptr = Initialised::scope(&mut b, Abort, |init,| {
+      let (v, uninit,) = init.take();
+      assert_eq!(v, 42, "Got incorrect value",);
+      let init = uninit.init(10,);
+      let ptr = &*init as *const i32;
+      (init, ptr,)
+    },);
+    assert_eq!(ptr, &b as *const i32, "Ptr does not line up",);
     assert_eq!(b, 10, "Set incorrect value",)
   }
+  #[test]
+  fn test_forwarding() {
+    use std::{format, vec,};
+
+    let (mut a, mut b,) = (1, 2,);
+    let (a, b,) = unsafe { (Initialised::new(&mut a,), Initialised::new(&mut b,),) };
+    assert_eq!(*a + 1, *b, "Incorrect deref",);
+    assert!(a < b, "Incorrect ordering",);
+    assert_ne!(a, b, "Incorrect equality",);
+    assert_eq!(format!("{} {:?}", a, b,), "1 2", "Incorrect formatting",);
+
+    let mut iter = vec![1, 2, 3,].into_iter();
+    let iter = unsafe { Initialised::new(&mut iter,) };
+    assert_eq!(iter.sum::<i32>(), 6, "Incorrect iteration",);
+  }
+  #[test]
+  fn test_project() {
+    use reinit_derive::Reinit;
+
+    #[derive(Reinit,)]
+    struct Named<T,>
+      where T: Copy, {
+      a: T,
+      b: i32,
+    }
+    #[derive(Reinit,)]
+    struct Tuple<'a, const N: usize,>(&'a str, [i32; N],);
+
+    let mut named = Named { a: 1u8, b: 2, };
+    Initialised::scope(&mut named, Abort, |init,| {
+      let (a, fields,) = init.project().take_a();
+      let (b, fields,) = fields.take_b();
+      let mut fields = fields.init_b(b + 1,).init_a(a + 1,);
+      *fields.b += 1;
+      (fields.join(), (),)
+    },);
+    assert_eq!((named.a, named.b,), (2, 4,), "Set incorrect values",);
+
+    let mut tuple = Tuple("a", [1,],);
+    Initialised::scope(&mut tuple, Abort, |init,| {
+      let (a, fields,) = init.project().take_0();
+      let fields = fields.init_0(&a[1..],);
+      (Initialised::join(fields,), (),)
+    },);
+    assert_eq!(tuple.0, "", "Set incorrect value",);
+
+    Initialised::scope(&mut tuple, Abort, |init,| {
+      let (_, uninit,) = init.take();
+      (uninit.project().init_1([5,],).init_0("b",).join(), (),)
+    },);
+    assert_eq!((tuple.0, tuple.1,), ("b", [5,],), "Set incorrect values",);
+  }
+  #[test]
+  #[should_panic]
+  fn test_project_swapped() {
+    use reinit_derive::Reinit;
+
+    #[derive(Reinit,)]
+    struct Pair {
+      a: i32,
+      b: i32,
+    }
+
+    let (mut x, mut y,) = (Pair { a: 1, b: 2, }, Pair { a: 3, b: 4, },);
+    let (mut x, mut y,) = unsafe {
+      (Initialised::new(&mut x,).project(), Initialised::new(&mut y,).project(),)
+    };
+    mem::swap(&mut x.a, &mut y.a,);
+    let _ = x.join();
+  }
+  #[test]
+  fn test_split() {
+    let mut pair = (1, 2,);
+    Initialised::scope(&mut pair, Abort, |init,| {
+      let ((a, b,), base,) = init.split();
+      let ((a, ua,), (b, ub,),) = (a.take(), b.take(),);
+      let (a, b,) = (ua.init(b,), ub.init(a,),);
+      (Initialised::join(((a, b,), base,),), (),)
+    },);
+    assert_eq!(pair, (2, 1,), "Set incorrect values",);
+
+    Initialised::scope(&mut pair, Abort, |init,| {
+      let ((a, b,), base,) = init.split();
+      let ((a, ua,), (b, ub,),) = (a.take(), b.take(),);
+      (Uninitialised::join(((ua, ub,), base,),).init((a * 10, b * 10,),), (),)
+    },);
+    assert_eq!(pair, (20, 10,), "Set incorrect values",);
+
+    let mut array = [1, 2, 3,];
+    Initialised::scope(&mut array, Abort, |init,| {
+      let (mut parts, base,) = init.split();
+      parts.swap(0, 2,);
+      let [a, b, c,] = parts;
+      let (a, b, c,) = (a.take(), b.take(), c.take(),);
+      let init = Uninitialised::join(([c.1, b.1, a.1,], base,),).init([a.0, b.0, c.0,],);
+      let ([a, b, c,], base,) = init.split();
+      (Initialised::join(([a, b, c,], base,),), (),)
+    },);
+    assert_eq!(array, [3, 2, 1,], "Set incorrect values",);
+  }
+  #[test]
+  #[should_panic]
+  fn test_split_swapped() {
+    let mut array = [1, 2,];
+    let ([a, b,], base,) = unsafe { Initialised::new(&mut array,) }.split();
+    let _ = Initialised::join(([b, a,], base,),);
+  }
+  #[test]
+  #[should_panic]
+  fn test_split_other_base() {
+    let (mut x, mut y,) = ((1, 2,), (3, 4,),);
+    let (x, _,) = unsafe { Initialised::new(&mut x,) }.split();
+    let (_, y,) = unsafe { Initialised::new(&mut y,) }.split();
+    let _ = Initialised::join((x, y,),);
+  }
+  #[test]
+  fn test_slice() {
+    let mut array = [1, 2, 3, 4, 5,];
+    Initialised::scope(&mut array[..], Abort, |init,| {
+      let ((left, right,), base,) = init.split_at(2,);
+      let elements = right.elements();
+      let mut rejoin = elements.rejoin();
+      for element in elements {
+        let (v, uninit,) = element.take();
+        rejoin.push(uninit.init(v * 10,),);
+      }
+
+      (Initialised::join(((left, rejoin.finish(),), base,),), (),)
+    },);
+    assert_eq!(array, [1, 2, 30, 40, 50,], "Set incorrect values",);
+
+    Initialised::scope(&mut array[..], Abort, |init,| {
+      let chunks = init.chunks(2,);
+      assert_eq!(chunks.len(), 3, "Incorrect chunk count",);
+      let mut rejoin = chunks.rejoin();
+      for mut chunk in chunks {
+        chunk.reverse();
+        rejoin.push_slice(chunk,);
+      }
+
+      (rejoin.finish(), (),)
+    },);
+    assert_eq!(array, [2, 1, 40, 30, 50,], "Set incorrect values",);
+
+    let mut empty: [i32; 0] = [];
+    Initialised::scope(&mut empty[..], Abort, |init,| {
+      let elements = init.elements();
+      (elements.rejoin().finish(), (),)
+    },);
+  }
+  #[test]
+  #[should_panic]
+  fn test_slice_rejoin_order() {
+    let mut array = [1, 2,];
+    let mut elements = unsafe { Initialised::new(&mut array[..],) }.elements();
+    let mut rejoin = elements.rejoin();
+    let first = elements.next().unwrap();
+    rejoin.push(elements.next().unwrap(),);
+    rejoin.push(first,);
+  }
+  #[test]
+  #[should_panic]
+  fn test_slice_other_base() {
+    let (mut x, mut y,) = ([1, 2,], [3, 4,],);
+    let ((x, _,), _,) = unsafe { Initialised::new(&mut x[..],) }.split_at(1,);
+    let ((_, y,), base,) = unsafe { Initialised::new(&mut y[..],) }.split_at(1,);
+    let _ = Initialised::join(((x, y,), base,),);
+  }
+  #[test]
+  fn test_uninit_slice() {
+    let mut values = [1, 2, 3,].map(std::boxed::Box::new,);
+    Initialised::scope(&mut values[..], Abort, |init,| {
+      let uninit = init.drop_value();
+      assert_eq!(uninit.len(), 3, "Incorrect length",);
+      let ((left, right,), base,) = uninit.split_at(1,);
+      let right = right.init_from_fn(std::boxed::Box::new,);
+      let uninit = Uninitialised::join(((left, right.drop_value(),), base,),);
+
+      (uninit.init_from_fn(|index,| std::boxed::Box::new(index * 10,),), (),)
+    },);
+    assert_eq!(values.map(|value,| *value,), [0, 10, 20,], "Set incorrect values",);
+
+    let mut num = 1;
+    let (_, uninit,) = unsafe { Initialised::new(&mut num,) }.take();
+    drop(Uninitialised::<[i32], DefaultOnDrop,>::from(uninit.with_policy(),),);
+    assert_eq!(num, 0, "Did not refill the element",);
+  }
+  #[test]
+  fn test_uninit_slice_panic() {
+    use std::rc::Rc;
+
+    let rc = Rc::new((),);
+    let mut values = [None, None, None,];
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      Initialised::scope(&mut values[..], RestoreDefault, |init,| {
+        let init = init.drop_value().init_from_fn(|index,| {
+          assert!(index < 2,);
+          Some(rc.clone(),)
+        },);
+        (init, (),)
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(Rc::strong_count(&rc,), 1, "Did not drop the written elements",);
+    assert_eq!(values, [None, None, None,], "Did not restore the defaults",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      let uninit = unsafe { Initialised::new(&mut values[..],) }.drop_value();
+      let _ = uninit.with_policy::<DefaultOnDrop>().init_from_fn(|index,| {
+        assert!(index < 1,);
+        Some(rc.clone(),)
+      },);
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(Rc::strong_count(&rc,), 1, "Did not drop the written elements",);
+    assert_eq!(values, [None, None, None,], "Did not apply the policy",);
+  }
+  #[test]
+  fn test_slice_scope_panic() {
+    let mut array = [1, 2, 3,];
+    let res = panic::catch_unwind::<_, (),>(AssertUnwindSafe(|| {
+      Initialised::scope(&mut array[..], RestoreDefault, |init,| {
+        let element = init.elements().nth(1,).unwrap();
+        let _ = element.take();
+        unreachable!()
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(array, [0, 0, 0,], "Did not restore the defaults",);
+  }
+  #[test]
+  fn test_hole() {
+    fn insertion_sort<T,>(
+      mut init: Initialised<[T],>,
+      mut less: impl FnMut(&T, &T,) -> bool,
+    ) -> Initialised<[T],> {
+      for index in 1..init.len() {
+        let mut hole = Hole::new(init, index,);
+        while hole.pos() > 0 && less(hole.element(), hole.get(hole.pos() - 1,),) {
+          hole.move_to(hole.pos() - 1,);
+        }
+        init = hole.fill();
+      }
+
+      init
+    }
+
+    let mut values = [5, 1, 4, 2, 3,];
+    Initialised::scope(&mut values[..], Abort, |init,| {
+      (insertion_sort(init, |a, b,| a < b,), (),)
+    },);
+    assert_eq!(values, [1, 2, 3, 4, 5,], "Did not sort",);
+
+    let mut values = [5, 1, 4, 2, 3,].map(std::boxed::Box::new,);
+    let mut comparisons = 0;
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      let init = unsafe { Initialised::new(&mut values[..],) };
+      insertion_sort(init, |a, b,| { comparisons += 1; assert!(comparisons < 4,); a < b },);
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    let mut values = values.map(|value,| *value,);
+    values.sort();
+    assert_eq!(values, [1, 2, 3, 4, 5,], "Lost an element",);
+  }
+  #[test]
+  fn test_cast() {
+    #[derive(Debug, PartialEq,)]
+    enum State { Idle(u32,), Running(u32,), }
+
+    let mut state = State::Idle(1,);
+    Initialised::scope(&mut state, Abort, |init,| {
+      let (state, uninit,) = init.take();
+      let count = match state { State::Idle(count,) | State::Running(count,) => count, };
+      let (count, uninit,) = uninit.cast::<(u32, u32,)>().init((count, 1,),).take();
+      (uninit.cast::<State>().init(State::Running(count.0 + count.1,),), (),)
+    },);
+    assert_eq!(state, State::Running(2,), "Set incorrect value",);
+
+    let mut num = 1u32;
+    let (_, uninit,) = unsafe { Initialised::new(&mut num,) }.take();
+    let uninit = match uninit.try_cast::<u64>() {
+      Ok(_,) => panic!("Cast to a different layout",),
+      Err(uninit,) => uninit,
+    };
+    match uninit.try_cast::<i32>() {
+      Ok(uninit,) => { let _ = uninit.init(-1,); },
+      Err(_,) => panic!("Failed to cast to the same layout",),
+    }
+    assert_eq!(num, u32::MAX, "Set incorrect value",);
+  }
+  #[cfg(feature = "alloc",)]
+  #[test]
+  fn test_map_in_place() {
+    use std::{rc::Rc, vec, vec::Vec,};
+
+    let values = vec![1u32, 2, 3,];
+    let ptr = values.as_ptr() as *const i32;
+    let values = map_in_place(values, |v,| -(v as i32),);
+    assert_eq!(values, [-1, -2, -3,], "Mapped incorrect values",);
+    assert_eq!(values.as_ptr(), ptr, "Did not reuse the allocation",);
+
+    let rc = Rc::new((),);
+    let values = vec![rc.clone(), rc.clone(), rc.clone(), rc.clone(),];
+    let mut count = 0;
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      map_in_place(values, |v,| {
+        count += 1;
+        assert!(count < 3,);
+        Some(v,)
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(Rc::strong_count(&rc,), 1, "Did not drop every element",);
+
+    let empty: Vec<u8> = map_in_place(Vec::<i8>::new(), |v,| v as u8,);
+    assert!(empty.is_empty(), "Produced elements",);
+  }
+  #[cfg(feature = "alloc",)]
+  #[test]
+  fn test_box_slot() {
+    use std::{boxed::Box, rc::Rc,};
+
+    let boxed = Box::new(3u32,);
+    let ptr = &*boxed as *const u32 as *const f32;
+    let (v, slot,) = BoxSlot::take(boxed,);
+    let boxed = slot.cast::<f32>().init(v as f32,);
+    assert_eq!(*boxed, 3.0, "Set incorrect value",);
+    assert_eq!(&*boxed as *const f32, ptr, "Did not reuse the allocation",);
+
+    let rc = Rc::new((),);
+    let (moved, slot,) = BoxSlot::take(Box::new(rc.clone(),),);
+    let slot = match slot.try_cast::<u8>() {
+      Ok(_,) => panic!("Cast to a different layout",),
+      Err(slot,) => slot,
+    };
+    drop(slot,);
+    assert_eq!(Rc::strong_count(&rc,), 2, "Dropped the moved value",);
+    drop(moved,);
+
+    let boxed = BoxSlot::<(),>::new().init((),);
+    assert_eq!(*boxed, (), "Set incorrect value",);
+  }
+  #[test]
+  fn test_slot() {
+    use core::{mem::MaybeUninit, pin::Pin,};
+
+    let mut num = 1;
+    let init = unsafe { Initialised::from_slot(Pin::new(&mut num,),) };
+    let (v, uninit,) = init.take();
+    let slot = uninit.init(v + 1,).into_slot();
+    assert_eq!(*slot, 2, "Set incorrect value",);
+
+    let mut out = MaybeUninit::new(1,);
+    let init = unsafe { Initialised::from_slot(MaybeUninitSlot::from(&mut out,),) };
+    let (v, uninit,) = init.take();
+    let _ = uninit.init(v * 10,);
+    assert_eq!(unsafe { out.assume_init() }, 10, "Set incorrect value",);
+
+    let mut num = 1;
+    let init = unsafe { Initialised::from_slot(ptr::NonNull::from(&mut num,),) };
+    let (_, uninit,) = init.take();
+    drop(uninit.with_policy::<DefaultOnDrop>(),);
+    assert_eq!(num, 0, "Did not apply the policy",);
+  }
+  #[cfg(feature = "alloc",)]
+  #[test]
+  fn test_box_slot_trait() {
+    use std::{boxed::Box, rc::Rc,};
+
+    let rc = Rc::new((),);
+    let init = Initialised::from(Box::new(rc.clone(),),);
+    assert_eq!(Rc::strong_count(&*init,), 2, "Incorrect deref",);
+    let (v, uninit,) = init.take();
+    let boxed = uninit.init(v,).into_slot();
+    drop(boxed,);
+    assert_eq!(Rc::strong_count(&rc,), 1, "Did not drop the box",);
+
+    let init = Initialised::from(Box::new(rc.clone(),),);
+    drop(init,);
+    assert_eq!(Rc::strong_count(&rc,), 1, "Did not drop the box",);
+  }
+  #[test]
+  fn test_maybe_uninit() {
+    use core::mem::MaybeUninit;
+
+    fn fill(out: &mut MaybeUninit<i32,>,) { out.write(5,); }
+
+    let mut out = MaybeUninit::uninit();
+    let mut uninit = Uninitialised::from_maybe_uninit(&mut out,);
+    fill(uninit.as_out(),);
+    let mut init = unsafe { uninit.assume_init() };
+    *init += 1;
+    let MaybeUninitSlot(out,) = init.into_slot();
+    assert_eq!(unsafe { out.assume_init_read() }, 6, "Set incorrect value",);
+
+    let mut out = MaybeUninit::<i32,>::uninit();
+    drop(Uninitialised::from_maybe_uninit(&mut out,).with_policy::<DefaultOnDrop>(),);
+    assert_eq!(unsafe { out.assume_init() }, 0, "Did not apply the policy",);
+  }
+  #[test]
+  fn test_raw() {
+    unsafe extern "C" fn increment(value: *mut i32,) {
+      let init = unsafe { Initialised::from_raw(value,) };
+      let (v, uninit,) = init.take();
+      uninit.init(v + 1,).into_raw();
+    }
+
+    let mut num = 1;
+    unsafe { increment(&mut num,) };
+    assert_eq!(num, 2, "Set incorrect value",);
+
+    let (_, uninit,) = unsafe { Initialised::new(&mut num,) }.take();
+    let raw = uninit.into_raw();
+    assert_eq!(raw, &mut num as *mut i32, "Incorrect pointer",);
+    unsafe { raw.write(3,) };
+    assert_eq!(num, 3, "Set incorrect value",);
+  }
+  #[test]
+  fn test_init_with() {
+    let mut pair = (1, [2; 3],);
+    Initialised::scope(&mut pair, Abort, |init,| {
+      let (_, uninit,) = init.take();
+      let ((a, b,), base,) = uninit.split();
+      let ([b0, b1, b2,], b,) = b.split();
+      let b0 = b0.init_with(|out,| out.write(3,),);
+      let init = Initialised::join(([b0, b1.init(4,), b2.init(5,),], b,),);
+      (Initialised::join(((a.init_with(|out,| out.write(0,),), init,), base,),), (),)
+    },);
+    assert_eq!(pair, (0, [3, 4, 5,],), "Set incorrect values",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      Initialised::scope(&mut pair.0, || 7, |init,| {
+        let (_, uninit,) = init.take();
+        let other = std::boxed::Box::leak(std::boxed::Box::new(0,),);
+        (uninit.init_with(move |_,| other,), (),)
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(pair.0, 7, "Did not refill the slot",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      Initialised::scope(&mut pair.0, || 8, |init,| {
+        let (_, uninit,) = init.take();
+        (uninit.with_policy::<PanicOnDrop>().init_with(|_,| panic!(),), (),)
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(pair.0, 8, "Did not refill the slot",);
+  }
+  #[test]
+  fn test_init_array() {
+    use core::convert::TryFrom;
+
+    let mut buffer = [0u16; 1024];
+    Initialised::scope(&mut buffer, Abort, |init,| {
+      let (_, uninit,) = init.take();
+      let init = Uninitialised::<[u16],>::from(uninit,).init_from_fn(|index,| index as u16,);
+      let init = Initialised::<[u16; 512],>::try_from(init,).unwrap_err();
+      (Initialised::try_from(init,).unwrap(), (),)
+    },);
+    let filled = buffer.iter().enumerate().all(|(index, &value,),| value as usize == index,);
+    assert!(filled, "Set incorrect values",);
+  }
+  #[test]
+  fn test_try_replace_with() {
+    let mut b = 42u32;
+    let res = Initialised::scope(&mut b, Abort, |init,| {
+      init.try_replace_with(Abort, |v,| if v > 0 { Ok(v + 1,) } else { Err((v, (),),) },)
+    },);
+    assert_eq!((b, res,), (43, Ok(()),), "Set incorrect value",);
+
+    let res = Initialised::scope(&mut b, Abort, |init,| {
+      let (v, uninit,) = init.take();
+      uninit.init_or_restore(v.checked_sub(50,).ok_or((v, "underflow",),),)
+    },);
+    assert_eq!((b, res,), (43, Err("underflow",),), "Did not restore the value",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      let init = unsafe { Initialised::new(&mut b,) };
+      let _ = init.try_replace_with::<_, _, (),>(RestoreDefault, |_,| panic!(),);
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(b, 0, "Did not restore the default",);
+  }
+  #[test]
+  fn test_transaction() {
+    use std::{string::String, vec, vec::Vec,};
+
+    let (mut a, mut b,) = (1, String::from("a",),);
+    let res = Initialised::scope(&mut a, Abort, |a,| {
+      Initialised::scope(&mut b, Abort, |b,| {
+        let ((a, b,), res,) = Transaction::new().with(a, Abort,).with(b, Abort,)
+          .run(|(a, b,),| Ok::<_, ((_, _,), (),)>((a + 1, b + "b",),),);
+        (b, (a, res,),)
+      },)
+    },);
+    assert_eq!((a, b.as_str(), res,), (2, "ab", Ok(()),), "Set incorrect values",);
+
+    let (mut a, mut b,) = (vec![1,], vec![2,],);
+    let res = Initialised::scope(&mut a, Abort, |a,| {
+      Initialised::scope(&mut b, Abort, |b,| {
+        let ((a, b,), res,) = Transaction::new().with_clone(a,).with_clone(b,)
+          .run(|(a, b,): (Vec<i32>, Vec<i32>,),| Err(((a, b,), "failed",),),);
+        (b, (a, res,),)
+      },)
+    },);
+    assert_eq!(res, Err("failed",), "Did not return the error",);
+    assert_eq!((a, b,), (vec![1,], vec![2,],), "Did not restore the values",);
+
+    let (mut a, mut b,) = (vec![1,], 5,);
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      let (a, b,) = unsafe { (Initialised::new(&mut a,), Initialised::new(&mut b,),) };
+      let _ = Transaction::new().with_clone(a,).with(b, RestoreDefault,)
+        .run::<_, (),>(|(mut a, _,),| { a.push(2,); panic!() },);
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!((a, b,), (vec![1,], 0,), "Did not restore the values",);
+  }
+  #[test]
+  fn test_take_all() {
+    use std::{string::String, vec,};
+
+    let (mut a, mut b, mut c,) = (1, String::from("a",), vec![1,],);
+    take_all!(&mut a, &mut b, &mut c => |(a, b, mut c,),| { c.push(a,); (a + 1, b + "b", c,) });
+    assert_eq!((a, b.as_str(), &*c,), (2, "ab", &[1, 1,][..],), "Set incorrect values",);
+
+    Initialised::scope(&mut a, Abort, |a,| {
+      let a = Initialised::scope(&mut b, Abort, |b,| {
+        let (a, b, _,) = take_all!(a, b, &mut c => |(a, b, c,),| (a * 10, b + "c", c,));
+        (b, a,)
+      },);
+      (a, (),)
+    },);
+    assert_eq!((a, b.as_str(),), (20, "abc",), "Set incorrect values",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      Initialised::scope(&mut a, || 7, |a,| {
+        let (a,) = take_all!(a => |_,| -> (i32,) { panic!() },);
+        (a, (),)
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(a, 7, "Did not refill the slot",);
+  }
+  #[test]
+  fn test_drop_value() {
+    use std::rc::Rc;
+
+    let rc = Rc::new((),);
+    let mut value = rc.clone();
+    Initialised::scope(&mut value, Abort, |init,| {
+      let uninit = init.drop_value();
+      assert_eq!(Rc::strong_count(&rc,), 1, "Did not drop the value",);
+      (uninit.init(rc.clone(),), (),)
+    },);
+    assert!(Rc::ptr_eq(&value, &rc,), "Set incorrect value",);
+    assert_eq!(Rc::strong_count(&rc,), 2, "Dropped the new value",);
+  }
+  #[test]
+  fn test_option() {
+    let mut option = Some(1,);
+    let (v, none,) = unsafe { Initialised::new(&mut option,) }.take_some().ok().unwrap();
+    drop(none,);
+    assert_eq!((v, option,), (1, None,), "Did not leave `None`",);
+
+    let mut option = None::<i32,>;
+    Initialised::scope(&mut option, Abort, |init,| {
+      let init = init.take_some().err().unwrap();
+      let (mut v, base,) = init.fill_none(1,).ok().unwrap();
+      *v += 1;
+      let (init, v,) = Initialised::join((v, base,),).fill_none(3,).err().unwrap();
+      assert_eq!((*init, v,), (Some(2,), 3,), "Filled a `Some`",);
+      (init, (),)
+    },);
+    assert_eq!(option, Some(2,), "Set incorrect value",);
+  }
+  #[test]
+  fn test_transition() {
+    use reinit_derive::Transition;
+    use std::{string::String, vec::Vec,};
+
+    #[derive(Transition, Debug, PartialEq,)]
+    enum State<T,> {
+      Idle(T,),
+      Running { buffer: Vec<T>, },
+      #[transition(fallback)]
+      Failed(String, u32,),
+    }
+
+    let mut state = State::Idle(1,);
+    Initialised::scope(&mut state, Abort, |init,| {
+      (init.transition(|state,| match state {
+        State::Idle(v,) => State::Running { buffer: std::vec![v,], },
+        state => state,
+      },), (),)
+    },);
+    assert_eq!(state, State::Running { buffer: std::vec![1,], }, "Set incorrect state",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      let init = unsafe { Initialised::new(&mut state,) };
+      init.transition(|_,| panic!(),);
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(state, State::Failed(String::new(), 0,), "Did not write the fallback",);
+  }
+  #[test]
+  fn test_take_copy() {
+    #[derive(Debug, PartialEq,)]
+    struct Point { x: i32, y: i32, }
+
+    unsafe impl TriviallyReinit for Point {}
+
+    let mut point = Point { x: 1, y: 2, };
+    Initialised::scope(&mut point, Abort, |mut init,| {
+      let Point { x, y, } = init.take_copy();
+      *init = Point { x: y, y: x, };
+      (init, (),)
+    },);
+    assert_eq!(point, Point { x: 2, y: 1, }, "Set incorrect value",);
+
+    Initialised::scope(&mut point, Abort, |init,| {
+      let (Point { x, y, }, stale,) = init.take_trivial();
+      (stale.init(Point { x: x * 10, y: y * 10, },), (),)
+    },);
+    assert_eq!(point, Point { x: 20, y: 10, }, "Set incorrect value",);
+
+    let mut num = 1;
+    let (v, stale,) = unsafe { Initialised::new(&mut num,) }.take_trivial();
+    drop(stale,);
+    assert_eq!((v, num,), (1, 1,), "Dropping the handle changed the value",);
+  }
+  #[test]
+  fn test_dyn_slot() {
+    let mut num = 1;
+    Initialised::scope(&mut num, Abort, |init,| {
+      let mut slot = DynSlot::from(init,);
+      assert_eq!(slot.take(), Some(1,), "Took the wrong value",);
+      assert!(!slot.is_filled(), "Still filled after `take`",);
+      assert_eq!(
+        (slot.get().copied(), slot.take(),), (None, None,),
+        "Got a value from an empty slot",
+      );
+      let slot = DynSlot::from(slot.into_uninitialised().unwrap(),);
+      let mut slot = slot.into_initialised().err().unwrap();
+      assert_eq!(slot.put(2,), None, "Replaced a value in an empty slot",);
+      assert_eq!(slot.put(3,), Some(2,), "Did not return the replaced value",);
+      assert_eq!(slot.get(), Some(&3,), "Got the wrong value",);
+      (slot.into_initialised().unwrap(), (),)
+    },);
+    assert_eq!(num, 3, "Set incorrect value",);
+
+    let mut slot = DynSlot::<_, DefaultOnDrop,>::from(
+      unsafe { Initialised::new(&mut num,) }.take().1.with_policy(),
+    );
+    slot.put(4,);
+    slot.take();
+    drop(slot,);
+    assert_eq!(num, 0, "Did not apply the drop policy",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      let slot = DynSlot::from(unsafe { Initialised::new(&mut num,) },);
+      slot.with_policy::<PanicOnDrop>().take();
+    },),);
+    assert!(res.is_err(), "Dropping an empty `DynSlot` did not panic",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      Initialised::scope(&mut num, || 7, |init,| {
+        let mut slot = DynSlot::from(init,);
+        for i in 0.. {
+          let v = slot.take().unwrap();
+          assert!(i < 2,);
+          slot.put(v + 1,);
+        }
+        (slot.into_initialised().ok().unwrap(), (),)
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(num, 7, "Did not refill the slot",);
+  }
+  #[test]
+  fn test_scope_panic() {
+    let mut b = 42;
+    let res = panic::catch_unwind::<_, (),>(AssertUnwindSafe(|| {
+      Initialised::scope(&mut b, || 10, |init,| {
+        let _ = init.take();
+        unreachable!()
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(b, 10, "Did not restore the fallback",);
+
+    let mut s = std::string::String::from("a",);
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      Initialised::scope(&mut s, RestoreDefault, |init,| {
+        let (s, uninit,) = init.take();
+        let n = s.parse::<u32>().unwrap();
+        (uninit.init(std::format!("{}", n + 1),), (),)
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert!(s.is_empty(), "Did not restore the default",);
+  }
+  #[test]
+  fn test_scope_wrong_slot() {
+    let mut b = 42;
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      Initialised::scope(&mut b, RestoreDefault, |init,| {
+        let (_, uninit,) = init.take();
+        mem::forget(uninit,);
+        (unsafe { Initialised::new(std::boxed::Box::leak(std::boxed::Box::new(1,),),) }, (),)
+      },)
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(b, 0, "Did not restore the default",);
+  }
+  #[cfg(feature = "nightly",)]
+  #[test]
+  fn test_const() {
+    const N: i32 = {
+      let mut n = 42;
+      let (v, uninit,) = unsafe { Initialised::new(&mut n,) }.take();
+      uninit.init(v + 1,).into_inner();
+      n
+    };
+
+    assert_eq!(N, 43, "Set incorrect value",);
+  }
+  #[test]
+  fn test_take_with() {
+    let mut b = 42;
+    Initialised::scope(&mut b, Abort, |init,| {
+      (init.take_with(Abort, |v,| v + 1,), (),)
+    },);
+    assert_eq!(b, 43, "Set incorrect value",);
+  }
+  #[test]
+  fn test_take_with_panic() {
+    let mut b = 42;
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      unsafe { Initialised::new(&mut b,) }.take_with(RestoreDefault, |_,| panic!(),);
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(b, 0, "Did not restore the default",);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+      unsafe { Initialised::new(&mut b,) }.take_with(|| 10, |_,| panic!(),);
+    },),);
+    assert!(res.is_err(), "Did not panic",);
+    assert_eq!(b, 10, "Did not restore the fallback",);
+  }
 }
@@ -0,0 +1,94 @@
+//! Moving values in and out of an `Option` without a drop policy.
+
+use crate::*;
+
+impl<'a, T,> Initialised<'a, Option<T>,> {
+  /// Moves the value out of a `Some` leaving `None`, or returns `self` if there is no value.
+  ///
+  /// Unlike [`Initialised::take`] the slot is left in a valid state so the returned handle
+  /// can be dropped freely.
+  ///
+  /// ```
+  /// use reinit::*;
+  ///
+  /// let mut state = Some(String::from("a"));
+  /// Initialised::scope(&mut state, Abort, |init| {
+  ///   let (s, none) = init.take_some().unwrap();
+  ///   let (s, base) = none.fill(s + "b");
+  ///   (Initialised::join((s, base)), ())
+  /// });
+  /// assert_eq!(state.as_deref(), Some("ab"));
+  /// ```
+  #[inline]
+  pub fn take_some(self,) -> Result<(T, NoneSlot<'a, T,>,), Self> {
+    let slot = self.into_inner();
+    match slot.take() {
+      Some(value,) => Ok((value, NoneSlot { slot, },),),
+      None => Err(unsafe { Initialised::new(slot,) },),
+    }
+  }
+  /// Fills a `None` with `value` returning a handle to the value inside the `Option`, or
+  /// returns `self` and `value` if there is already a value.
+  ///
+  /// The handles can be rejoined using [`Initialised::join`].
+  #[inline]
+  pub fn fill_none(
+    self,
+    value: T,
+  ) -> Result<(Initialised<'a, T,>, SomeBase<'a, T,>,), (Self, T,)> {
+    let slot = self.into_inner();
+    if slot.is_some() { return Err((unsafe { Initialised::new(slot,) }, value,),) }
+
+    Ok(NoneSlot { slot, }.fill(value,),)
+  }
+}
+
+/// A reference to an `Option` which is `None`.
+///
+/// `None` is a valid value so this can be dropped without reinitialising the slot.
+#[must_use]
+pub struct NoneSlot<'a, T,> {
+  /// The reference.
+  slot: &'a mut Option<T>,
+}
+
+impl<'a, T,> NoneSlot<'a, T,> {
+  /// Fills the slot with `value` returning a handle to the value inside the `Option`.
+  ///
+  /// The handles can be rejoined using [`Initialised::join`].
+  #[inline]
+  pub fn fill(self, value: T,) -> (Initialised<'a, T,>, SomeBase<'a, T,>,) {
+    let option = self.slot as *mut Option<T>;
+    let slot = unsafe { (*option).insert(value,) as *mut T };
+
+    (unsafe { Initialised::new(&mut *slot,) }, SomeBase { option, slot, _phantom: PhantomData, },)
+  }
+  /// Returns a handle to the `None`.
+  #[inline]
+  pub fn into_initialised(self,) -> Initialised<'a, Option<T>,> {
+    unsafe { Initialised::new(self.slot,) }
+  }
+}
+
+/// The `Option` containing a value filled using [`NoneSlot::fill`].
+#[must_use]
+pub struct SomeBase<'a, T,> {
+  /// The `Option`.
+  option: *mut Option<T>,
+  /// The value inside the `Option`.
+  slot: *mut T,
+  _phantom: PhantomData<&'a mut Option<T>>,
+}
+
+impl<'a, T,> Join for (Initialised<'a, T,>, SomeBase<'a, T,>,) {
+  type Joined = Initialised<'a, Option<T>,>;
+
+  #[track_caller]
+  #[inline]
+  fn join(self,) -> Self::Joined {
+    let (init, base,) = self;
+    check_slot(init.into_raw(), base.slot,);
+
+    unsafe { Initialised::new(&mut *base.option,) }
+  }
+}
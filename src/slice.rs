@@ -0,0 +1,436 @@
+//! Splitting slices into handles for subslices and elements.
+
+use crate::*;
+use core::{convert::TryFrom, iter::FusedIterator, mem::MaybeUninit,};
+
+impl<'a, T,> Initialised<'a, [T],> {
+  /// Splits the slice into two at `mid` along with the [`Base`] needed to rejoin them.
+  ///
+  /// The halves can be rejoined using [`Initialised::join`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `mid > len`.
+  #[track_caller]
+  #[inline]
+  pub fn split_at(self, mid: usize,) -> ((Self, Self,), Base<'a, [T],>,) {
+    assert!(mid <= self.len(), "Split position out of bounds",);
+
+    let base = Base::new(self,);
+    let (left, right,) = split_at(base.as_ptr(), mid,);
+
+    unsafe { ((Initialised::new(&mut *left,), Initialised::new(&mut *right,),), base,) }
+  }
+  /// Splits the slice into chunks of `size` elements, the last chunk may be shorter.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `size` is `0`.
+  #[track_caller]
+  #[inline]
+  pub fn chunks(self, size: usize,) -> Chunks<'a, T,> {
+    assert!(size != 0, "Chunk size must be non-zero",);
+
+    Chunks { elements: self.elements(), size, }
+  }
+  /// Splits the slice into a handle for each element.
+  ///
+  /// ```
+  /// use reinit::*;
+  ///
+  /// let mut strings = [String::from("a"), String::from("b")];
+  /// Initialised::scope(&mut strings[..], Abort, |init| {
+  ///   let elements = init.elements();
+  ///   let mut rejoin = elements.rejoin();
+  ///   for element in elements {
+  ///     let (s, uninit) = element.take();
+  ///     rejoin.push(uninit.init(s + "!"));
+  ///   }
+  ///   (rejoin.finish(), ())
+  /// });
+  /// assert_eq!(strings, ["a!", "b!"]);
+  /// ```
+  #[inline]
+  pub fn elements(self,) -> Elements<'a, T,> {
+    let slot = self.into_inner();
+
+    Elements { slot: slot.as_mut_ptr(), start: 0, end: slot.len(), _phantom: PhantomData, }
+  }
+}
+
+impl<'a, T, S,> Initialised<'a, [T], S,>
+  where S: Slot<Target = [T],>, {
+  /// Drops every element in place and leaves the slice uninitialised.
+  ///
+  /// The process aborts if a destructor panics as the slice cannot be refilled.
+  #[track_caller]
+  #[inline]
+  pub fn drop_value(self,) -> Uninitialised<'a, [T], LeakOnDrop, S,> {
+    let slot = self.into_raw();
+    let guard = Guard::new(slot, Abort,);
+    unsafe { ptr::drop_in_place(slot,) }
+    guard.forget();
+
+    Uninitialised {
+      slot,
+      origin: Origin::new::<[T],>(),
+      _phantom: PhantomData,
+      _policy: PhantomData,
+    }
+  }
+}
+
+impl<'a, T,> Join for ((Initialised<'a, [T],>, Initialised<'a, [T],>,), Base<'a, [T],>,) {
+  type Joined = Initialised<'a, [T],>;
+
+  #[track_caller]
+  #[inline]
+  fn join(self,) -> Self::Joined {
+    let ((left, right,), base,) = self;
+    check_halves(left.slot, right.slot, base.as_ptr(),);
+
+    unsafe { base.into_initialised() }
+  }
+}
+
+impl<'a, T, P,> Uninitialised<'a, [T], P,>
+  where P: DropPolicy<T,>, {
+  /// The number of elements in the slice.
+  #[inline]
+  pub fn len(&self,) -> usize { self.slot.len() }
+  /// Returns `true` if the slice has no elements.
+  #[inline]
+  pub fn is_empty(&self,) -> bool { self.len() == 0 }
+  /// Splits the slice into two at `mid` along with the [`Base`] needed to rejoin them.
+  ///
+  /// The halves can be rejoined using [`Uninitialised::join`] or, once initialised,
+  /// [`Initialised::join`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `mid > len`.
+  #[track_caller]
+  #[inline]
+  pub fn split_at(self, mid: usize,) -> ((Self, Self,), Base<'a, [T],>,) {
+    assert!(mid <= self.len(), "Split position out of bounds",);
+
+    let base = Base::from_uninit(self,);
+    let (left, right,) = split_at(base.as_ptr(), mid,);
+    let (left, right,) = unsafe {
+      (Uninitialised::from_raw(left,), Uninitialised::from_raw(right,),)
+    };
+    ((left.with_policy(), right.with_policy(),), base,)
+  }
+  /// Returns the slice as `MaybeUninit`s so it can be written through an out parameter.
+  #[inline]
+  pub fn as_out(&mut self,) -> &mut [MaybeUninit<T>] {
+    unsafe { &mut *(self.slot as *mut [MaybeUninit<T>]) }
+  }
+  /// Reinitialises each element in place with the result of passing its index to `f`.
+  ///
+  /// If `f` panics the elements already written are dropped and the handle is dropped using
+  /// its policy before unwinding continues.
+  ///
+  /// ```
+  /// use reinit::*;
+  ///
+  /// let mut names = [String::from("a"), String::from("b")];
+  /// Initialised::scope(&mut names[..], Abort, |init| {
+  ///   (init.drop_value().init_from_fn(|index| index.to_string()), ())
+  /// });
+  /// assert_eq!(names, ["0", "1"]);
+  /// ```
+  ///
+  /// Large arrays can be filled in place by converting them to slices:
+  ///
+  /// ```
+  /// use std::convert::TryFrom;
+  /// use reinit::*;
+  ///
+  /// let mut buffer = [0u8; 4096];
+  /// Initialised::scope(&mut buffer, Abort, |init| {
+  ///   let (_, uninit) = init.take();
+  ///   let init = Uninitialised::<[u8]>::from(uninit).init_from_fn(|index| index as u8);
+  ///   (Initialised::try_from(init).unwrap(), ())
+  /// });
+  /// assert_eq!(buffer[257], 1);
+  /// ```
+  #[inline]
+  pub fn init_from_fn<F,>(self, mut f: F,) -> Initialised<'a, [T],>
+    where F: FnMut(usize,) -> T, {
+    let mut guard = FillGuard { uninit: mem::ManuallyDrop::new(self,), written: 0, };
+    let (slot, len,) = (guard.uninit.slot as *mut T, guard.uninit.len(),);
+    while guard.written < len {
+      unsafe { ptr::write(slot.add(guard.written,), f(guard.written,),) }
+      guard.written += 1;
+    }
+
+    let mut guard = mem::ManuallyDrop::new(guard,);
+    unsafe { mem::ManuallyDrop::take(&mut guard.uninit,).assume_init() }
+  }
+}
+
+/// Drops the elements written to a partially initialised slice and then drops its handle.
+struct FillGuard<'a, T, P,>
+  where P: DropPolicy<T,>, {
+  /// The slice being initialised.
+  uninit: mem::ManuallyDrop<Uninitialised<'a, [T], P,>>,
+  /// The number of elements written.
+  written: usize,
+}
+
+impl<T, P,> Drop for FillGuard<'_, T, P,>
+  where P: DropPolicy<T,>, {
+  #[inline]
+  fn drop(&mut self,) {
+    unsafe {
+      ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.uninit.slot as *mut T, self.written,),);
+      mem::ManuallyDrop::drop(&mut self.uninit,);
+    }
+  }
+}
+
+impl<'a, T, P,> Join
+  for ((Uninitialised<'a, [T], P,>, Uninitialised<'a, [T], P,>,), Base<'a, [T],>,)
+  where P: DropPolicy<T,>, {
+  type Joined = Uninitialised<'a, [T], P,>;
+
+  #[track_caller]
+  #[inline]
+  fn join(self,) -> Self::Joined {
+    let ((left, right,), base,) = self;
+    check_halves(left.into_raw(), right.into_raw(), base.as_ptr(),);
+
+    base.into_uninitialised()
+  }
+}
+
+impl<'a, T, P,> From<Uninitialised<'a, T, P,>> for Uninitialised<'a, [T], P,>
+  where P: DropPolicy<T,>, {
+  #[inline]
+  fn from(from: Uninitialised<'a, T, P,>,) -> Self {
+    let Uninitialised { slot, origin, .. } = *mem::ManuallyDrop::new(from,);
+    let slot = ptr::slice_from_raw_parts_mut(slot, 1,);
+
+    Uninitialised { slot, origin, _phantom: PhantomData, _policy: PhantomData, }
+  }
+}
+
+impl<'a, T, P, const N: usize,> From<Uninitialised<'a, [T; N], P,>> for Uninitialised<'a, [T], P,>
+  where P: DropPolicy<T,> + DropPolicy<[T; N],>, {
+  #[inline]
+  fn from(from: Uninitialised<'a, [T; N], P,>,) -> Self {
+    let Uninitialised { slot, origin, .. } = *mem::ManuallyDrop::new(from,);
+
+    Uninitialised { slot, origin, _phantom: PhantomData, _policy: PhantomData, }
+  }
+}
+
+/// Splits the slice at `slot` into two at `mid`.
+#[inline]
+fn split_at<T,>(slot: *mut [T], mid: usize,) -> (*mut [T], *mut [T],) {
+  let start = slot as *mut T;
+  (
+    ptr::slice_from_raw_parts_mut(start, mid,),
+    ptr::slice_from_raw_parts_mut(start.wrapping_add(mid,), slot.len() - mid,),
+  )
+}
+
+/// Panics if `left` and `right` are not the halves of `slot`.
+#[track_caller]
+#[inline]
+fn check_halves<T,>(left: *mut [T], right: *mut [T], slot: *mut [T],) {
+  if left.len() > slot.len() { panic!("Rejoined a handle from a different slot",) }
+
+  let (expected_left, expected_right,) = split_at(slot, left.len(),);
+  check_slot(left, expected_left,);
+  check_slot(right, expected_right,);
+}
+
+/// An iterator over handles to the elements of a slice.
+///
+/// See [`Initialised::elements`].
+#[must_use]
+pub struct Elements<'a, T,> {
+  /// The start of the slice.
+  slot: *mut T,
+  /// The index of the next element.
+  start: usize,
+  /// The index past the last element.
+  end: usize,
+  _phantom: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T,> Elements<'a, T,> {
+  /// Returns a [`Rejoin`] for the elements remaining in this iterator.
+  #[inline]
+  pub fn rejoin(&self,) -> Rejoin<'a, T,> {
+    Rejoin {
+      slot: self.slot.wrapping_add(self.start,),
+      len: 0,
+      end: self.end - self.start,
+      _phantom: PhantomData,
+    }
+  }
+  /// Returns a handle to the remaining elements.
+  #[inline]
+  pub fn into_slice(self,) -> Initialised<'a, [T],> {
+    let slot = ptr::slice_from_raw_parts_mut(self.slot.wrapping_add(self.start,), self.len(),);
+
+    unsafe { Initialised::new(&mut *slot,) }
+  }
+}
+
+impl<'a, T,> Iterator for Elements<'a, T,> {
+  type Item = Initialised<'a, T,>;
+
+  #[inline]
+  fn next(&mut self,) -> Option<Self::Item> {
+    if self.start == self.end { return None }
+
+    let element = unsafe { Initialised::new(&mut *self.slot.add(self.start,),) };
+    self.start += 1;
+    Some(element,)
+  }
+  #[inline]
+  fn size_hint(&self,) -> (usize, Option<usize>,) { (self.len(), Some(self.len(),),) }
+}
+
+impl<T,> DoubleEndedIterator for Elements<'_, T,> {
+  #[inline]
+  fn next_back(&mut self,) -> Option<Self::Item> {
+    if self.start == self.end { return None }
+
+    self.end -= 1;
+    Some(unsafe { Initialised::new(&mut *self.slot.add(self.end,),) },)
+  }
+}
+
+impl<T,> ExactSizeIterator for Elements<'_, T,> {
+  #[inline]
+  fn len(&self,) -> usize { self.end - self.start }
+}
+
+impl<T,> FusedIterator for Elements<'_, T,> {}
+
+/// An iterator over handles to chunks of a slice.
+///
+/// See [`Initialised::chunks`].
+#[must_use]
+pub struct Chunks<'a, T,> {
+  /// The remaining elements.
+  elements: Elements<'a, T,>,
+  /// The length of each chunk.
+  size: usize,
+}
+
+impl<'a, T,> Chunks<'a, T,> {
+  /// Returns a [`Rejoin`] for the chunks remaining in this iterator.
+  #[inline]
+  pub fn rejoin(&self,) -> Rejoin<'a, T,> { self.elements.rejoin() }
+}
+
+impl<'a, T,> Iterator for Chunks<'a, T,> {
+  type Item = Initialised<'a, [T],>;
+
+  #[inline]
+  fn next(&mut self,) -> Option<Self::Item> {
+    if self.elements.len() == 0 { return None }
+
+    let len = usize::min(self.size, self.elements.len(),);
+    let start = self.elements.slot.wrapping_add(self.elements.start,);
+    let slot = ptr::slice_from_raw_parts_mut(start, len,);
+    self.elements.start += len;
+    Some(unsafe { Initialised::new(&mut *slot,) },)
+  }
+  #[inline]
+  fn size_hint(&self,) -> (usize, Option<usize>,) {
+    let len = self.elements.len().div_ceil(self.size,);
+
+    (len, Some(len,),)
+  }
+}
+
+impl<T,> ExactSizeIterator for Chunks<'_, T,> {}
+
+impl<T,> FusedIterator for Chunks<'_, T,> {}
+
+/// Rejoins handles to consecutive elements or subslices back into a handle to a slice.
+///
+/// Obtained from [`Elements::rejoin`] or [`Chunks::rejoin`]; the handles must be pushed in
+/// order.
+#[must_use]
+pub struct Rejoin<'a, T,> {
+  /// The start of the slice.
+  slot: *mut T,
+  /// The number of elements rejoined so far.
+  len: usize,
+  /// The number of elements to rejoin.
+  end: usize,
+  _phantom: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T,> Rejoin<'a, T,> {
+  /// Rejoins the next element.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `element` is not the next element of the slice.
+  #[track_caller]
+  #[inline]
+  pub fn push(&mut self, element: Initialised<'a, T,>,) {
+    self.push_slice(Initialised::from(element,),)
+  }
+  /// Rejoins the next subslice.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `slice` does not start at the next element of the slice.
+  #[track_caller]
+  #[inline]
+  pub fn push_slice(&mut self, slice: Initialised<'a, [T],>,) {
+    let slice = slice.into_inner();
+    check_slot(slice.as_ptr(), self.slot.wrapping_add(self.len,),);
+    assert!(slice.len() <= self.end - self.len, "Rejoined a handle past the end of the slice",);
+
+    self.len += slice.len();
+  }
+  /// Returns a handle to the rejoined slice.
+  ///
+  /// # Panics
+  ///
+  /// Panics if not every element has been rejoined.
+  #[track_caller]
+  #[inline]
+  pub fn finish(self,) -> Initialised<'a, [T],> {
+    assert!(self.len == self.end, "Not every element was rejoined",);
+    let slot = ptr::slice_from_raw_parts_mut(self.slot, self.len,);
+
+    unsafe { Initialised::new(&mut *slot,) }
+  }
+}
+
+impl<'a, T,> From<Initialised<'a, T,>> for Initialised<'a, [T],> {
+  #[inline]
+  fn from(from: Initialised<'a, T,>,) -> Self {
+    unsafe { Initialised::new(core::slice::from_mut(from.into_inner(),),) }
+  }
+}
+
+impl<'a, T, const N: usize,> From<Initialised<'a, [T; N],>> for Initialised<'a, [T],> {
+  #[inline]
+  fn from(from: Initialised<'a, [T; N],>,) -> Self {
+    unsafe { Initialised::new(from.into_inner(),) }
+  }
+}
+
+impl<'a, T, const N: usize,> TryFrom<Initialised<'a, [T],>> for Initialised<'a, [T; N],> {
+  type Error = Initialised<'a, [T],>;
+
+  #[inline]
+  fn try_from(from: Initialised<'a, [T],>,) -> Result<Self, Self::Error> {
+    if from.len() != N { return Err(from) }
+
+    Ok(unsafe { Initialised::from_raw(from.into_raw() as *mut [T; N],) },)
+  }
+}
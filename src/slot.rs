@@ -0,0 +1,94 @@
+//! Storage which can be referenced by an `Initialised` or `Uninitialised`.
+
+use crate::*;
+use core::{mem::MaybeUninit, pin::Pin,};
+
+/// Owned or borrowed storage for a `Target`.
+///
+/// An [`Initialised`] or [`Uninitialised`] holds its slot as a raw pointer and converts it
+/// back when dropped, so an owning slot such as a `Box` is freed along with the handle.
+///
+/// # Safety
+///
+/// The pointer returned by `into_raw` must be valid for reads and writes of a `Target` until
+/// it is passed to `from_raw`, which must return the slot it came from.
+pub unsafe trait Slot: Sized {
+  /// The type stored in the slot.
+  type Target: ?Sized;
+
+  /// Converts the slot into a pointer to its storage.
+  fn into_raw(self,) -> *mut Self::Target;
+  /// Converts a pointer returned by [`Slot::into_raw`] back into the slot.
+  ///
+  /// # Safety
+  ///
+  /// `raw` must have been returned by `into_raw` for this type of slot.
+  unsafe fn from_raw(raw: *mut Self::Target,) -> Self;
+}
+
+unsafe impl<T: ?Sized,> Slot for &'_ mut T {
+  type Target = T;
+
+  #[inline]
+  fn into_raw(self,) -> *mut T { self }
+  #[inline]
+  unsafe fn from_raw(raw: *mut T,) -> Self { &mut *raw }
+}
+
+/// A `&mut MaybeUninit<T>` used as a slot for a `T`.
+///
+/// A `&mut MaybeUninit<T>` is itself a slot for a `MaybeUninit<T>`, so this wrapper is needed
+/// to reference the `T` inside.
+#[repr(transparent,)]
+pub struct MaybeUninitSlot<'a, T,>(pub &'a mut MaybeUninit<T>,);
+
+impl<'a, T,> From<&'a mut MaybeUninit<T>> for MaybeUninitSlot<'a, T,> {
+  #[inline]
+  fn from(from: &'a mut MaybeUninit<T>,) -> Self { Self(from,) }
+}
+
+unsafe impl<T,> Slot for MaybeUninitSlot<'_, T,> {
+  type Target = T;
+
+  #[inline]
+  fn into_raw(self,) -> *mut T { self.0.as_mut_ptr() }
+  #[inline]
+  unsafe fn from_raw(raw: *mut T,) -> Self { Self(&mut *(raw as *mut MaybeUninit<T>),) }
+}
+
+unsafe impl<T: ?Sized,> Slot for ptr::NonNull<T> {
+  type Target = T;
+
+  #[inline]
+  fn into_raw(self,) -> *mut T { self.as_ptr() }
+  #[inline]
+  unsafe fn from_raw(raw: *mut T,) -> Self { ptr::NonNull::new_unchecked(raw,) }
+}
+
+unsafe impl<T: ?Sized,> Slot for Pin<&'_ mut T>
+  where T: Unpin, {
+  type Target = T;
+
+  #[inline]
+  fn into_raw(self,) -> *mut T { Pin::into_inner(self,) as &mut T }
+  #[inline]
+  unsafe fn from_raw(raw: *mut T,) -> Self { Pin::new(&mut *raw,) }
+}
+
+#[cfg(feature = "alloc",)]
+unsafe impl<T: ?Sized,> Slot for alloc::boxed::Box<T> {
+  type Target = T;
+
+  #[inline]
+  fn into_raw(self,) -> *mut T { alloc::boxed::Box::into_raw(self,) }
+  #[inline]
+  unsafe fn from_raw(raw: *mut T,) -> Self { alloc::boxed::Box::from_raw(raw,) }
+}
+
+//The `Box` is owned so leaking an `Uninitialised` can only leak the allocation.
+#[cfg(feature = "alloc",)]
+impl<'a, T: 'a + ?Sized,> From<alloc::boxed::Box<T>>
+  for Initialised<'a, T, alloc::boxed::Box<T>,> {
+  #[inline]
+  fn from(from: alloc::boxed::Box<T>,) -> Self { unsafe { Initialised::from_slot(from,) } }
+}
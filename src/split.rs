@@ -0,0 +1,179 @@
+//! Splitting tuples and arrays into per-element handles.
+
+use crate::*;
+
+macro_rules! tuples {
+  ($(($($T:ident $P:ident $i:tt,)+),)+) => {
+    $(tuples! { @impl ($($T,)+); $($T $P $i,)+ })+
+  };
+  (@impl $Tuple:ty; $($T:ident $P:ident $i:tt,)+) => {
+    unsafe impl<$($T,)+> Reinit for $Tuple {
+      type Fields<'a,> = (($(Initialised<'a, $T,>,)+), Base<'a, Self,>,)
+        where Self: 'a;
+      type UninitFields<'a,> = (($(Uninitialised<'a, $T,>,)+), Base<'a, Self,>,)
+        where Self: 'a;
+
+      #[inline]
+      fn project<'a,>(init: Initialised<'a, Self,>,) -> Self::Fields<'a,> {
+        let base = Base::new(init,);
+        let slot = base.as_ptr();
+
+        unsafe { (($(Initialised::new(&mut *ptr::addr_of_mut!((*slot).$i),),)+), base,) }
+      }
+      #[inline]
+      fn project_uninit<'a, P,>(uninit: Uninitialised<'a, Self, P,>,) -> Self::UninitFields<'a,>
+        where P: DropPolicy<Self,>, { uninit.split() }
+    }
+
+    impl<'a, $($T,)+> Join for (($(Initialised<'a, $T,>,)+), Base<'a, $Tuple,>,) {
+      type Joined = Initialised<'a, $Tuple,>;
+
+      #[track_caller]
+      #[inline]
+      fn join(self,) -> Self::Joined {
+        let (parts, base,) = self;
+        let slot = base.as_ptr();
+        $(check_slot(parts.$i.slot, unsafe { ptr::addr_of_mut!((*slot).$i) },);)+
+
+        unsafe { base.into_initialised() }
+      }
+    }
+
+    impl<'a, $($T, $P,)+> Join for (($(Uninitialised<'a, $T, $P,>,)+), Base<'a, $Tuple,>,)
+      where $($P: DropPolicy<$T,>,)+ {
+      type Joined = Uninitialised<'a, $Tuple,>;
+
+      #[track_caller]
+      #[inline]
+      fn join(self,) -> Self::Joined {
+        let (parts, base,) = self;
+        let (parts, slot,) = (mem::ManuallyDrop::new(parts,), base.as_ptr(),);
+        $(check_slot(parts.$i.slot, unsafe { ptr::addr_of_mut!((*slot).$i) },);)+
+
+        base.into_uninitialised()
+      }
+    }
+
+    impl<'a, $($T,)+> Initialised<'a, $Tuple,> {
+      /// Splits the tuple into a handle for each element along with the [`Base`] needed to
+      /// rejoin them.
+      ///
+      /// The handles can be rejoined using [`Initialised::join`] and once taken the
+      /// uninitialised elements can be recombined using [`Uninitialised::join`].
+      #[inline]
+      pub fn split(self,) -> (($(Initialised<'a, $T,>,)+), Base<'a, $Tuple,>,) { self.project() }
+    }
+
+    impl<'a, $($T,)+ P,> Uninitialised<'a, $Tuple, P,>
+      where P: DropPolicy<$Tuple,>, {
+      /// Splits the uninitialised tuple into a handle for each element so that it can be
+      /// initialised in place.
+      ///
+      /// Once initialised the elements can be rejoined with the [`Base`] using
+      /// [`Initialised::join`].
+      #[track_caller]
+      #[inline]
+      pub fn split(self,) -> (($(Uninitialised<'a, $T,>,)+), Base<'a, $Tuple,>,) {
+        let base = Base::from_uninit(self,);
+        let slot = base.as_ptr();
+
+        unsafe { (($(Uninitialised::from_raw(ptr::addr_of_mut!((*slot).$i),),)+), base,) }
+      }
+    }
+  };
+}
+
+tuples! {
+  (A PA 0,),
+  (A PA 0, B PB 1,),
+  (A PA 0, B PB 1, C PC 2,),
+  (A PA 0, B PB 1, C PC 2, D PD 3,),
+  (A PA 0, B PB 1, C PC 2, D PD 3, E PE 4,),
+  (A PA 0, B PB 1, C PC 2, D PD 3, E PE 4, F PF 5,),
+  (A PA 0, B PB 1, C PC 2, D PD 3, E PE 4, F PF 5, G PG 6,),
+  (A PA 0, B PB 1, C PC 2, D PD 3, E PE 4, F PF 5, G PG 6, H PH 7,),
+  (A PA 0, B PB 1, C PC 2, D PD 3, E PE 4, F PF 5, G PG 6, H PH 7, I PI 8,),
+  (A PA 0, B PB 1, C PC 2, D PD 3, E PE 4, F PF 5, G PG 6, H PH 7, I PI 8, J PJ 9,),
+  (A PA 0, B PB 1, C PC 2, D PD 3, E PE 4, F PF 5, G PG 6, H PH 7, I PI 8, J PJ 9,
+    K PK 10,),
+  (A PA 0, B PB 1, C PC 2, D PD 3, E PE 4, F PF 5, G PG 6, H PH 7, I PI 8, J PJ 9,
+    K PK 10, L PL 11,),
+}
+
+unsafe impl<T, const N: usize,> Reinit for [T; N] {
+  type Fields<'a,> = ([Initialised<'a, T,>; N], Base<'a, Self,>,)
+    where Self: 'a;
+  type UninitFields<'a,> = ([Uninitialised<'a, T,>; N], Base<'a, Self,>,)
+    where Self: 'a;
+
+  #[inline]
+  fn project<'a,>(init: Initialised<'a, Self,>,) -> Self::Fields<'a,> {
+    let base = Base::new(init,);
+    let slot = base.as_ptr() as *mut T;
+
+    (core::array::from_fn(|index,| unsafe { Initialised::new(&mut *slot.add(index,),) },), base,)
+  }
+  #[inline]
+  fn project_uninit<'a, P,>(uninit: Uninitialised<'a, Self, P,>,) -> Self::UninitFields<'a,>
+    where P: DropPolicy<Self,>, { uninit.split() }
+}
+
+impl<'a, T, const N: usize,> Join for ([Initialised<'a, T,>; N], Base<'a, [T; N],>,) {
+  type Joined = Initialised<'a, [T; N],>;
+
+  #[track_caller]
+  #[inline]
+  fn join(self,) -> Self::Joined {
+    let (parts, base,) = self;
+    let slot = base.as_ptr() as *mut T;
+    for (index, part,) in parts.iter().enumerate() {
+      check_slot(part.slot, slot.wrapping_add(index,),);
+    }
+
+    unsafe { base.into_initialised() }
+  }
+}
+
+impl<'a, T, P, const N: usize,> Join for ([Uninitialised<'a, T, P,>; N], Base<'a, [T; N],>,)
+  where P: DropPolicy<T,>, {
+  type Joined = Uninitialised<'a, [T; N],>;
+
+  #[track_caller]
+  #[inline]
+  fn join(self,) -> Self::Joined {
+    let (parts, base,) = self;
+    let (parts, slot,) = (mem::ManuallyDrop::new(parts,), base.as_ptr() as *mut T,);
+    for (index, part,) in parts.iter().enumerate() {
+      check_slot(part.slot, slot.wrapping_add(index,),);
+    }
+
+    base.into_uninitialised()
+  }
+}
+
+impl<'a, T, const N: usize,> Initialised<'a, [T; N],> {
+  /// Splits the array into a handle for each element along with the [`Base`] needed to
+  /// rejoin them.
+  ///
+  /// The handles can be rejoined using [`Initialised::join`] and once taken the
+  /// uninitialised elements can be recombined using [`Uninitialised::join`].
+  #[inline]
+  pub fn split(self,) -> ([Initialised<'a, T,>; N], Base<'a, [T; N],>,) { self.project() }
+}
+
+impl<'a, T, P, const N: usize,> Uninitialised<'a, [T; N], P,>
+  where P: DropPolicy<[T; N],>, {
+  /// Splits the uninitialised array into a handle for each element so that it can be
+  /// initialised in place.
+  ///
+  /// Once initialised the elements can be rejoined with the [`Base`] using
+  /// [`Initialised::join`].
+  #[track_caller]
+  #[inline]
+  pub fn split(self,) -> ([Uninitialised<'a, T,>; N], Base<'a, [T; N],>,) {
+    let base = Base::from_uninit(self,);
+    let slot = base.as_ptr() as *mut T;
+
+    (core::array::from_fn(|index,| unsafe { Uninitialised::from_raw(slot.add(index,),) },), base,)
+  }
+}
@@ -0,0 +1,151 @@
+//! Taking and reinitialising several handles together.
+
+use crate::*;
+
+/// Takes the values from several handles, possibly of different types, and reinitialises
+/// every handle together.
+///
+/// Each handle is added with [`Transaction::with`] along with the [`OnPanic`] used to refill
+/// it if the closure passed to [`Transaction::run`] panics; [`Transaction::with_clone`] keeps
+/// a clone of the original value to restore instead.
+///
+/// Only handles added using `with_clone` are restored to their original values on a panic,
+/// those added using `with` are refilled with whatever their `OnPanic` produces. On failure
+/// the values returned alongside the error are written back unchecked so the closure is
+/// responsible for returning the originals.
+///
+/// ```
+/// use reinit::*;
+///
+/// let (mut from, mut to) = (vec![1, 2], Vec::new());
+/// let res = Initialised::scope(&mut from, Abort, |from| {
+///   Initialised::scope(&mut to, Abort, |to| {
+///     let ((from, to), res) = Transaction::new().with_clone(from).with_clone(to)
+///       .run(|(mut from, mut to)| match from.pop() {
+///         Some(v) if v % 2 == 0 => { to.push(v); Ok((from, to)) },
+///         Some(v) => { from.push(v); Err(((from, to), "odd")) },
+///         None => Err(((from, to), "empty")),
+///       });
+///     (to, (from, res))
+///   })
+/// });
+/// assert_eq!(res, Ok(()));
+/// assert_eq!((from, to), (vec![1], vec![2]));
+/// ```
+#[must_use]
+pub struct Transaction<H, P,> {
+  /// The handles.
+  handles: H,
+  /// The strategies used to refill each handle.
+  on_panic: P,
+}
+
+impl Transaction<(), (),> {
+  /// Constructs an empty `Transaction`.
+  #[inline]
+  pub const fn new() -> Self { Self { handles: (), on_panic: (), } }
+}
+
+impl Default for Transaction<(), (),> {
+  #[inline]
+  fn default() -> Self { Self::new() }
+}
+
+macro_rules! transaction {
+  (
+    [$($l:lifetime $T:ident $S:ident $P:ident $i:tt,)*]
+    $nl:lifetime $NT:ident $NS:ident $NP:ident $ni:tt,
+    $($rest:tt)*
+  ) => {
+    impl<$($l,)* $($T, $S, $P,)*> Transaction<($(Initialised<$l, $T, $S,>,)*), ($($P,)*),>
+      where $($S: Slot<Target = $T,>, $P: OnPanic<$T,>,)* {
+      /// Adds a handle which is refilled using `on_panic` if the transaction panics.
+      #[inline]
+      pub fn with<$nl, $NT, $NS, $NP,>(
+        self,
+        init: Initialised<$nl, $NT, $NS,>,
+        on_panic: $NP,
+      ) -> Transaction<
+        ($(Initialised<$l, $T, $S,>,)* Initialised<$nl, $NT, $NS,>,),
+        ($($P,)* $NP,),
+      >
+        where $NS: Slot<Target = $NT,>, $NP: OnPanic<$NT,>, {
+        Transaction {
+          handles: ($(self.handles.$i,)* init,),
+          on_panic: ($(self.on_panic.$i,)* on_panic,),
+        }
+      }
+      /// Adds a handle which is restored to a clone of its current value if the transaction
+      /// panics.
+      #[inline]
+      pub fn with_clone<$nl, $NT, $NS,>(
+        self,
+        init: Initialised<$nl, $NT, $NS,>,
+      ) -> Transaction<
+        ($(Initialised<$l, $T, $S,>,)* Initialised<$nl, $NT, $NS,>,),
+        ($($P,)* Restore<$NT,>,),
+      >
+        where $NS: Slot<Target = $NT,>, $NT: Clone, {
+        let original = Restore((*init).clone(),);
+
+        self.with(init, original,)
+      }
+    }
+
+    impl<$($l,)* $nl, $($T, $S, $P,)* $NT, $NS, $NP,>
+      Transaction<($(Initialised<$l, $T, $S,>,)* Initialised<$nl, $NT, $NS,>,), ($($P,)* $NP,),>
+      where $($S: Slot<Target = $T,>, $P: OnPanic<$T,>,)* $NS: Slot<Target = $NT,>,
+        $NP: OnPanic<$NT,>, {
+      /// Passes the values of every handle to `f` and reinitialises each handle with the
+      /// values it returns.
+      ///
+      /// On failure `f` returns the values to restore along with the error; these are written
+      /// back as they are, so the originals are only restored if `f` returns them. If `f`
+      /// panics each handle is refilled using its [`OnPanic`] which only restores the original
+      /// value for handles added using [`Transaction::with_clone`].
+      pub fn run<F, E,>(
+        self,
+        f: F,
+      ) -> (($(Initialised<$l, $T, $S,>,)* Initialised<$nl, $NT, $NS,>,), Result<(), E>,)
+        where F: FnOnce(($($T,)* $NT,),) -> Result<($($T,)* $NT,), (($($T,)* $NT,), E,)>, {
+        let Self { handles, on_panic, } = self;
+        let guards = (
+          $(Guard::new(handles.$i.slot, on_panic.$i,),)*
+          Guard::new(handles.$ni.slot, on_panic.$ni,),
+        );
+        let values = unsafe { ($(ptr::read(handles.$i.slot,),)* ptr::read(handles.$ni.slot,),) };
+        let (values, res,) = match f(values,) {
+          Ok(values,) => (values, Ok(()),),
+          Err((values, e,),) => (values, Err(e,),),
+        };
+        $(guards.$i.forget();)*
+        guards.$ni.forget();
+        unsafe {
+          $(ptr::write(handles.$i.slot, values.$i,);)*
+          ptr::write(handles.$ni.slot, values.$ni,);
+        }
+
+        (handles, res,)
+      }
+    }
+
+    transaction! { [$($l $T $S $P $i,)* $nl $NT $NS $NP $ni,] $($rest)* }
+  };
+  ([$($l:lifetime $T:ident $S:ident $P:ident $i:tt,)*]) => {};
+}
+
+transaction! {
+  []
+  'a A SA PA 0,
+  'b B SB PB 1,
+  'c C SC PC 2,
+  'd D SD PD 3,
+  'e E0 SE PE 4,
+  'f F0 SF PF 5,
+  'g G SG PG 6,
+  'h H SH PH 7,
+  'i I SI PI 8,
+  'j J SJ PJ 9,
+  'k K SK PK 10,
+  'l L SL PL 11,
+}
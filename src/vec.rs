@@ -0,0 +1,73 @@
+//! Mapping the elements of a `Vec` in place.
+
+use crate::*;
+use alloc::vec::Vec;
+
+/// Maps each element of `vec` using `f` reusing its allocation.
+///
+/// `U` must have the same size and alignment as `T`, this is checked at compile time.
+///
+/// If `f` panics the elements already mapped and those not yet mapped are dropped before
+/// unwinding continues.
+///
+/// ```
+/// let strings = vec![String::from("a"), String::from("b")];
+/// let ptr = strings.as_ptr() as *const u8;
+/// let bytes = reinit::map_in_place(strings, |s| s.into_bytes());
+/// assert_eq!(bytes, [b"a", b"b"]);
+/// assert_eq!(bytes.as_ptr() as *const u8, ptr);
+/// ```
+pub fn map_in_place<T, U, F,>(vec: Vec<T>, mut f: F,) -> Vec<U>
+  where F: FnMut(T,) -> U, {
+  let mut vec = mem::ManuallyDrop::new(vec,);
+  let mut guard = MapGuard::<T, U,> {
+    slot: vec.as_mut_ptr(),
+    len: vec.len(),
+    capacity: vec.capacity(),
+    mapped: 0,
+    _phantom: PhantomData,
+  };
+  while guard.mapped < guard.len {
+    let element = unsafe { Initialised::new(&mut *guard.slot.add(guard.mapped,),) };
+    let (value, uninit,) = element.take();
+    //The guard is responsible for the slot if `f` panics.
+    let uninit = mem::ManuallyDrop::new(uninit.cast::<U>(),);
+    let value = f(value,);
+    let _ = mem::ManuallyDrop::into_inner(uninit,).init(value,);
+    guard.mapped += 1;
+  }
+
+  let guard = mem::ManuallyDrop::new(guard,);
+  unsafe { Vec::from_raw_parts(guard.slot as *mut U, guard.len, guard.capacity,) }
+}
+
+/// Drops the elements of a partially mapped `Vec` and frees its allocation when dropped.
+struct MapGuard<T, U,> {
+  /// The start of the allocation.
+  slot: *mut T,
+  /// The number of elements.
+  len: usize,
+  /// The capacity of the allocation.
+  capacity: usize,
+  /// The number of elements mapped to `U`s.
+  mapped: usize,
+  _phantom: PhantomData<U>,
+}
+
+impl<T, U,> Drop for MapGuard<T, U,> {
+  #[inline]
+  fn drop(&mut self,) {
+    unsafe {
+      //Frees the allocation even if a destructor panics.
+      let _vec = Vec::from_raw_parts(self.slot, 0, self.capacity,);
+      //The element being mapped has been moved into the panicking closure.
+      let rest = ptr::slice_from_raw_parts_mut(
+        self.slot.add(self.mapped + 1,),
+        self.len - self.mapped - 1,
+      );
+      let mapped = ptr::slice_from_raw_parts_mut(self.slot as *mut U, self.mapped,);
+      ptr::drop_in_place(mapped,);
+      ptr::drop_in_place(rest,);
+    }
+  }
+}